@@ -1,4 +1,6 @@
 fn main() {
+    probe_std_backtrace();
+
     #[cfg(feature = "c_api")]
     {
         // Find out whether we're in debug or release mode.
@@ -11,6 +13,35 @@ fn main() {
     }
 }
 
+/// `std::backtrace::Backtrace` has been stable since Rust 1.65. Emits
+/// `cfg(std_backtrace)` when the compiler building us is at least that new,
+/// so `src/backtrace.rs` can prefer it over the `backtrace`-crate-based
+/// fallback it otherwise falls back to under the `backtrace` feature.
+fn probe_std_backtrace() {
+    println!("cargo:rustc-check-cfg=cfg(std_backtrace)");
+
+    if rustc_version().is_some_and(|version| version >= (1, 65, 0)) {
+        println!("cargo:rustc-cfg=std_backtrace");
+    }
+}
+
+/// Parses the `major.minor.patch` triple out of `rustc --version`, run via
+/// `$RUSTC` (falling back to `rustc` on `$PATH`). Returns `None` if the
+/// compiler can't be invoked or its output doesn't parse, in which case the
+/// caller should assume the feature in question isn't available.
+fn rustc_version() -> Option<(u32, u32, u32)> {
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = std::process::Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    let version = version.split_whitespace().nth(1)?.split('-').next()?;
+
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?.parse().ok()?;
+    Some((major, minor, patch))
+}
+
 #[cfg(feature = "c_api")]
 fn run_cbindgen() {
     let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
@@ -155,10 +155,32 @@ pub fn c_api_wrapper_derive(input: TokenStream) -> TokenStream {
             _ => panic!("Unit structs not supported"),
         },
         Data::Enum(data) => {
+            let tags: Vec<Option<u32>> = data.variants.iter().map(variant_tag).collect();
+            let any_tagged = tags.iter().any(Option::is_some);
+            if any_tagged && tags.iter().any(Option::is_none) {
+                panic!(
+                    "{}: every variant must carry a #[c_api(tag = N)] when any variant does",
+                    name
+                );
+            }
+
+            // Rust only allows an explicit discriminant on a fieldless
+            // variant, so data-carrying variants (the common case here, e.g.
+            // `Pd3p2FixedSupplyPdo(FixedSupplyPdo)`) can't be tagged this
+            // way in the generated `repr(C)` enum itself; the tag still
+            // becomes available to C callers through the companion tag enum
+            // and accessor emitted below.
             let variants: Vec<TokenStream2> = data
                 .variants
                 .iter()
-                .map(prefix_enum_variants)
+                .zip(&tags)
+                .map(|(variant, tag)| {
+                    let variant_tokens = prefix_enum_variants(variant);
+                    match (tag, &variant.fields) {
+                        (Some(tag), Fields::Unit) => quote! { #variant_tokens = #tag },
+                        _ => variant_tokens,
+                    }
+                })
                 .collect();
 
             let from_old_match_arms: Vec<_> = data
@@ -179,6 +201,49 @@ pub fn c_api_wrapper_derive(input: TokenStream) -> TokenStream {
                 })
                 .collect();
 
+            // When the variants are explicitly tagged, emit a companion
+            // C-visible tag enum plus a `*_tag()` accessor, so C callers can
+            // dispatch on which variant they received without relying on
+            // cbindgen's implicit, reorder-sensitive discriminant ordering.
+            let tag_enum = if any_tagged {
+                let tag_enum_name = format_ident!("{}Type", new_name);
+                let tag_variants: Vec<_> = data
+                    .variants
+                    .iter()
+                    .zip(&tags)
+                    .map(|(Variant { ident, .. }, tag)| quote! { #ident = #tag })
+                    .collect();
+                let tag_match_arms: Vec<_> = data
+                    .variants
+                    .iter()
+                    .map(|Variant { ident, fields, .. }| match fields {
+                        Fields::Unit => quote! { #new_name::#ident => #tag_enum_name::#ident },
+                        _ => quote! { #new_name::#ident(..) => #tag_enum_name::#ident },
+                    })
+                    .collect();
+                let tag_fn_name = format_ident!("{}_tag", to_snake_case(&new_name.to_string()));
+
+                quote! {
+                    #[cfg(feature = "c_api")]
+                    #[repr(C)]
+                    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+                    pub(crate) enum #tag_enum_name {
+                        #(#tag_variants),*
+                    }
+
+                    #[cfg(feature = "c_api")]
+                    impl #new_name {
+                        pub(crate) fn #tag_fn_name(&self) -> #tag_enum_name {
+                            match self {
+                                #(#tag_match_arms),*
+                            }
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
             quote! {
                     #[cfg(feature = "c_api")]
                     #repr_c_token
@@ -187,6 +252,8 @@ pub fn c_api_wrapper_derive(input: TokenStream) -> TokenStream {
                         #(#variants),*
                     }
 
+                    #tag_enum
+
                     #[cfg(feature = "c_api")]
                     impl From<#name> for #new_name {
                         fn from(item: #name) -> Self {
@@ -272,6 +339,236 @@ fn prefix_struct_field_types(opts: &WrapperOpts, f: &Field) -> TokenStream2 {
     }
 }
 
+/// Parses the `#[bits(n)]` / `#[bits(n, reserved)]` / `#[bits(n, value = k)]`
+/// attribute syntax. Hand-rolled rather than via `darling`, since darling has
+/// no ergonomic support for a leading positional literal mixed with named
+/// flags.
+struct BitsAttr {
+    width: u8,
+    reserved: bool,
+    value: Option<u32>,
+}
+
+impl syn::parse::Parse for BitsAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let width: syn::LitInt = input.parse()?;
+        let width = width.base10_parse()?;
+
+        let mut reserved = false;
+        let mut value = None;
+        while input.parse::<syn::Token![,]>().is_ok() {
+            let ident: Ident = input.parse()?;
+            if ident == "reserved" {
+                reserved = true;
+            } else if ident == "value" {
+                input.parse::<syn::Token![=]>()?;
+                let lit: syn::LitInt = input.parse()?;
+                value = Some(lit.base10_parse()?);
+            } else {
+                return Err(syn::Error::new(ident.span(), "expected `reserved` or `value`"));
+            }
+        }
+
+        Ok(Self {
+            width,
+            reserved,
+            value,
+        })
+    }
+}
+
+struct BitFieldOpts {
+    ident: Ident,
+    ty: syn::Type,
+    width: u8,
+    reserved: bool,
+    value: Option<u32>,
+}
+
+fn parse_bits_attr(field: &Field) -> BitFieldOpts {
+    let attr = field
+        .attrs
+        .iter()
+        .find(|a| a.path().is_ident("bits"))
+        .unwrap_or_else(|| panic!("field `{:?}` is missing a #[bits(n)] attribute", field.ident));
+    let parsed: BitsAttr = attr.parse_args().expect("invalid #[bits(..)] attribute");
+
+    BitFieldOpts {
+        ident: field.ident.clone().expect("BitCodec requires named fields"),
+        ty: field.ty.clone(),
+        width: parsed.width,
+        reserved: parsed.reserved,
+        value: parsed.value,
+    }
+}
+
+/// Derive both `FromBytes` and `ToBytes` for a struct from `#[bits(n)]`
+/// annotations on each field, in the spirit of how `prost-derive` drives
+/// encode/decode entirely off field attributes.
+///
+/// Fields are read/written top to bottom, MSB-first, matching
+/// `bitstream_io::BitRead`/`BitWrite`:
+///
+/// - `bool` fields consume 1 bit.
+/// - Integer fields consume `n` bits into the field's own primitive type.
+/// - `enumn::N` enums consume `n` bits, then call `T::n(raw)`, returning
+///   `Error::ParseError` if the discriminant is unknown.
+/// - `BcdWrapper` and other unit newtypes consume `n` bits and wrap the
+///   result.
+///
+/// `#[bits(n, reserved)]` skips `n` bits on read (without binding a value)
+/// and writes `n` zero bits. `#[bits(n, value = k)]` asserts the field
+/// equals `k` on read and always emits `k` on write.
+///
+/// A `repr(C)` struct whose declared widths don't sum to a whole number of
+/// bytes is a compile error, since such a struct can't be round-tripped
+/// through a byte-oriented transport.
+#[proc_macro_derive(BitCodec, attributes(bits))]
+pub fn bit_codec_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        _ => panic!("BitCodec only supports structs"),
+    };
+    let fields = match &data.fields {
+        Fields::Named(fields) => &fields.named,
+        _ => panic!("BitCodec only supports structs with named fields"),
+    };
+
+    let is_repr_c = input.attrs.iter().any(|attr| {
+        attr.path().is_ident("repr") && quote!(#attr).to_string().contains('C')
+    });
+
+    let opts: Vec<BitFieldOpts> = fields.iter().map(parse_bits_attr).collect();
+
+    let total_bits: u32 = opts.iter().map(|o| o.width as u32).sum();
+    if is_repr_c && total_bits % 8 != 0 {
+        let msg = format!(
+            "#[derive(BitCodec)] on a repr(C) struct must declare bit widths summing to a \
+             whole number of bytes, got {total_bits} bits"
+        );
+        return TokenStream::from(quote! { compile_error!(#msg); });
+    }
+
+    let mut read_stmts = Vec::new();
+    let mut field_inits = Vec::new();
+    let mut write_stmts = Vec::new();
+
+    for opt in &opts {
+        let ident = &opt.ident;
+        let width = opt.width;
+        let ty = &opt.ty;
+
+        if opt.reserved {
+            read_stmts.push(quote! { reader.skip(#width as u32)?; });
+            write_stmts.push(quote! { bit_writer.write(#width, 0u32)?; });
+            // Reserved fields still exist on the struct (so callers can see
+            // the gap documented), they just aren't bound to a value read
+            // off the wire.
+            field_inits.push(quote! { #ident: ::std::default::Default::default() });
+            continue;
+        }
+
+        if let Some(value) = opt.value {
+            read_stmts.push(quote! {
+                let raw = reader.read::<u32>(#width as u32)?;
+                if raw != #value {
+                    return Err(crate::Error::ParseError {
+                        field: stringify!(#ident).into(),
+                        value: raw,
+                        #[cfg(feature = "backtrace")]
+                        backtrace: crate::backtrace::Backtrace::capture(),
+                    });
+                }
+            });
+            write_stmts.push(quote! { bit_writer.write(#width, #value)?; });
+            field_inits.push(quote! { #ident: #value as #ty });
+            continue;
+        }
+
+        field_inits.push(quote! { #ident });
+
+        let ty_string = quote! { #ty }.to_string();
+        if ty_string == "bool" {
+            read_stmts.push(quote! { let #ident = reader.read_bit()?; });
+            write_stmts.push(quote! { bit_writer.write_bit(self.#ident)?; });
+        } else if ty_string == "BcdWrapper" {
+            read_stmts.push(quote! { let #ident = BcdWrapper(reader.read(#width as u32)?); });
+            write_stmts.push(quote! { bit_writer.write(#width, self.#ident.0)?; });
+        } else if matches!(ty_string.as_str(), "u8" | "u16" | "u32" | "u64") {
+            // A plain integer field: read/write `width` bits straight into
+            // (out of) its own primitive type, no `enumn::N` lookup.
+            read_stmts.push(quote! { let #ident: #ty = reader.read(#width as u32)?; });
+            write_stmts.push(quote! { bit_writer.write(#width, self.#ident)?; });
+        } else {
+            read_stmts.push(quote! {
+                let raw = reader.read::<u32>(#width as u32)?;
+                let #ident = #ty::n(raw).ok_or_else(|| crate::Error::ParseError {
+                    field: stringify!(#ident).into(),
+                    value: raw,
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                })?;
+            });
+            write_stmts.push(quote! { bit_writer.write(#width, self.#ident as u32)?; });
+        }
+    }
+
+    let expanded = quote! {
+        impl crate::FromBytes for #name {
+            fn from_bytes(reader: &mut crate::BitReader) -> crate::Result<Self> {
+                #(#read_stmts)*
+                Ok(Self { #(#field_inits),* })
+            }
+        }
+
+        impl crate::ToBytes for #name {
+            fn to_bytes(&self, bit_writer: &mut crate::BitWriter) -> crate::Result<()> {
+                #(#write_stmts)*
+                Ok(())
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Reads a variant-level `#[c_api(tag = N)]` attribute, if present.
+fn variant_tag(variant: &Variant) -> Option<u32> {
+    variant.attrs.iter().find_map(|attr| {
+        if !attr.path().is_ident("c_api") {
+            return None;
+        }
+        let mut tag = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("tag") {
+                let value: syn::LitInt = meta.value()?.parse()?;
+                tag = Some(value.base10_parse()?);
+            }
+            Ok(())
+        })
+        .ok()?;
+        tag
+    })
+}
+
+fn to_snake_case(s: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in s.char_indices() {
+        if c.is_uppercase() {
+            if i != 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
 // Enum variants must use a type alias to be named the same as the C type.
 // A "c_api::" prefix is appended to disambiguate the wrapper from the alias.
 //
@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Pure parsing helpers for sysfs attribute contents.
+//!
+//! These take the already-read string contents of a sysfs file and produce
+//! the crate's typed values. They know nothing about how the bytes were
+//! obtained, so both the blocking [`super::sysfs`] reader and the async
+//! [`super::sysfs_async`] reader can share them instead of re-implementing
+//! the same parsing twice.
+
+use std::io;
+
+use crate::ucsi::CablePropertyPlugEndType;
+use crate::ucsi::CablePropertyType;
+use crate::ucsi::ConnectorCapabilityOperationMode;
+use crate::BcdWrapper;
+use crate::Error;
+use crate::Result;
+
+pub fn parse_bcd(content: &str) -> Result<BcdWrapper> {
+    let mut chars = content.chars();
+
+    let high = chars
+        .next()
+        .ok_or(io::Error::new(io::ErrorKind::InvalidData, "File is empty"))?;
+    let _ = chars.next().ok_or(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "File is too short",
+    ))?;
+
+    // Sometimes we get simply "2"
+    let low = chars.next().unwrap_or('0');
+
+    let high = high.to_digit(10).ok_or(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid digit: {high}"),
+    ))?;
+    let low = low.to_digit(10).ok_or(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid digit: {low}"),
+    ))?;
+
+    let bcd = (high << 8) | low;
+
+    Ok(BcdWrapper(bcd))
+}
+
+pub fn parse_opr(content: &str) -> Result<ConnectorCapabilityOperationMode> {
+    if content.contains("source") {
+        if content.contains("sink") {
+            Ok(ConnectorCapabilityOperationMode::Drp)
+        } else {
+            Ok(ConnectorCapabilityOperationMode::RpOnly)
+        }
+    } else {
+        Ok(ConnectorCapabilityOperationMode::RdOnly)
+    }
+}
+
+pub fn parse_pd_revision(content: &str) -> Result<u8> {
+    let mut chars = content.chars();
+
+    let b0 = chars.next().ok_or(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "File is too short",
+    ))?;
+    let _ = chars.next().ok_or(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "File is too short",
+    ))?;
+    let b2 = chars.next().ok_or(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "File is too short",
+    ))?;
+
+    let rev = ((b0 as u8 - b'0') << 4) | (b2 as u8 - b'0');
+    Ok(rev)
+}
+
+pub fn parse_hex_u32(content: &str) -> Result<u32> {
+    let content = content.replace("0x", "");
+    let hex = u32::from_str_radix(content.trim(), 16)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Could not parse hex value"))?;
+    Ok(hex)
+}
+
+pub fn parse_u32(content: &str) -> Result<u32> {
+    let mut content = content.to_string();
+    content.retain(|c| c.is_ascii_digit());
+
+    let dword = content
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Could not parse u32 value"))?;
+    Ok(dword)
+}
+
+pub fn parse_bit(content: &str) -> Result<bool> {
+    let bit = content
+        .trim()
+        .parse::<bool>()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Could not parse bool value"))?;
+    Ok(bit)
+}
+
+pub fn parse_cable_plug_type(content: &str) -> Result<CablePropertyPlugEndType> {
+    let plug_type = if content.contains("type-c") {
+        CablePropertyPlugEndType::UsbTypeC
+    } else if content.contains("type-a") {
+        CablePropertyPlugEndType::UsbTypeA
+    } else if content.contains("type-b") {
+        CablePropertyPlugEndType::UsbTypeB
+    } else {
+        CablePropertyPlugEndType::OtherNotUsb
+    };
+
+    Ok(plug_type)
+}
+
+pub fn parse_cable_type(content: &str) -> Result<CablePropertyType> {
+    if content.contains("active") {
+        Ok(CablePropertyType::Active)
+    } else if content.contains("passive") {
+        Ok(CablePropertyType::Passive)
+    } else {
+        Err(Error::ParseStringError {
+            field: "cable_type".to_string(),
+            value: content.to_string(),
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })
+    }
+}
+
+pub fn parse_cable_mode_support(content: &str) -> Result<bool> {
+    match content.chars().next() {
+        Some('0') => Ok(false),
+        Some(_) => Ok(true),
+        None => Err(Error::ParseStringError {
+            field: "cable_mode_support".to_string(),
+            value: content.to_string(),
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        }),
+    }
+}
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Platform backends implementing [`crate::OsBackend`].
+
+pub(crate) mod decode;
+pub mod snapshot;
+pub mod sysfs;
+#[cfg(feature = "async")]
+pub mod sysfs_async;
+pub mod uevent;
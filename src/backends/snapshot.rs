@@ -0,0 +1,721 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Capturing the sysfs Type-C tree into a portable archive, and replaying
+//! that archive through the same [`OsBackend`] surface as
+//! [`super::sysfs::SysfsBackend`].
+//!
+//! Reproducing a field report usually requires the exact hardware.
+//! [`Snapshot::capture`] walks `/sys/class/typec` (and the referenced
+//! `/sys/class/power_supply` entries) and records every attribute file's
+//! path and contents into a zstd-compressed, indexed archive, so the whole
+//! tree fits in one file someone can attach to a bug report. [`ReplayBackend`]
+//! then serves `OsBackend` reads straight out of that archive, exercising
+//! the real decode paths without touching a filesystem at all.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::backends::decode;
+use crate::backends::sysfs::SYSFS_PSY_PATH;
+use crate::backends::sysfs::SYSFS_TYPEC_PATH;
+use crate::pd::Pd3p2BatterySupplyPdo;
+use crate::pd::Pd3p2DiscoverIdentityResponse;
+use crate::pd::Pd3p2FastRoleSwap;
+use crate::pd::Pd3p2FixedSupplyPdo;
+use crate::pd::Pd3p2VariableSupplyPdo;
+use crate::pd::PdPdo;
+use crate::ucsi::ConnectorCapabilityOperationMode;
+use crate::ucsi::GetAlternateModesRecipient;
+use crate::ucsi::GetPdoSourceCapabilitiesType;
+use crate::ucsi::GetPdosSrcOrSink;
+use crate::ucsi::PdMessage;
+use crate::ucsi::PdMessageRecipient;
+use crate::ucsi::PdMessageResponseType;
+use crate::ucsi::UcsiAlternateMode;
+use crate::ucsi::UcsiCableProperty;
+use crate::ucsi::UcsiCapability;
+use crate::ucsi::UcsiConnectorCapability;
+use crate::ucsi::UcsiConnectorStatus;
+use crate::vdo::Pd3p2CertStatVdo;
+use crate::vdo::Pd3p2IdHeaderVdo;
+use crate::vdo::Pd3p2ProductTypeVdo;
+use crate::vdo::Pd3p2ProductVdo;
+use crate::BcdWrapper;
+use crate::BitReader;
+use crate::Error;
+use crate::FromBytes;
+use crate::OsBackend;
+use crate::Result;
+
+const MAGIC: &[u8; 8] = b"TCSNAP1\0";
+
+/// One captured attribute file's location within the archive's payload.
+struct IndexEntry {
+    path: String,
+    offset: u64,
+    compressed_len: u32,
+    uncompressed_len: u32,
+}
+
+/// A captured snapshot of the sysfs Type-C tree: every attribute file's
+/// path and contents, held decompressed in memory once loaded.
+pub struct Snapshot {
+    entries: BTreeMap<String, Vec<u8>>,
+}
+
+impl Snapshot {
+    /// Walks the real sysfs tree and captures every attribute file under
+    /// `/sys/class/typec` and `/sys/class/power_supply`.
+    pub fn capture() -> Result<Self> {
+        let mut entries = BTreeMap::new();
+
+        for root in [SYSFS_TYPEC_PATH, SYSFS_PSY_PATH] {
+            if !Path::new(root).exists() {
+                continue;
+            }
+            for entry in walkdir::WalkDir::new(root) {
+                let entry = entry.map_err(|source| Error::DirError {
+                    source: Box::new(source),
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                })?;
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let Ok(contents) = std::fs::read(entry.path()) else {
+                    // Some attributes (e.g. write-only ones) can't be read;
+                    // skip rather than fail the whole capture.
+                    continue;
+                };
+                entries.insert(entry.path().to_string_lossy().into_owned(), contents);
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Serializes this snapshot into a zstd-compressed, indexed archive:
+    /// every entry is compressed independently and located through an offset
+    /// table. [`Snapshot::load`] decompresses every entry up front; single
+    /// entries are not looked up through the offset table.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut index = Vec::new();
+        let mut payload = Vec::new();
+
+        for (entry_path, contents) in &self.entries {
+            let compressed = zstd::encode_all(contents.as_slice(), 0)?;
+            index.push(IndexEntry {
+                path: entry_path.clone(),
+                offset: payload.len() as u64,
+                compressed_len: compressed.len() as u32,
+                uncompressed_len: contents.len() as u32,
+            });
+            payload.extend_from_slice(&compressed);
+        }
+
+        let mut file = File::create(path)?;
+        file.write_all(MAGIC)?;
+        file.write_all(&(index.len() as u32).to_le_bytes())?;
+        for entry in &index {
+            file.write_all(&(entry.path.len() as u32).to_le_bytes())?;
+            file.write_all(entry.path.as_bytes())?;
+            file.write_all(&entry.offset.to_le_bytes())?;
+            file.write_all(&entry.compressed_len.to_le_bytes())?;
+            file.write_all(&entry.uncompressed_len.to_le_bytes())?;
+        }
+        file.write_all(&payload)?;
+
+        Ok(())
+    }
+
+    /// Reads back an archive written by [`Snapshot::save`].
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut file = File::open(path)?;
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+
+        if contents.get(..MAGIC.len()) != Some(MAGIC.as_slice()) {
+            return Err(Error::ParseStringError {
+                field: "magic".to_string(),
+                value: "not a typec snapshot archive".to_string(),
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            });
+        }
+
+        let mut cursor = MAGIC.len();
+        let count = read_u32(&contents, &mut cursor)?;
+
+        let mut index = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let path_len = read_u32(&contents, &mut cursor)? as usize;
+            let path_bytes = contents.get(cursor..cursor + path_len).ok_or_else(truncated)?;
+            let path = String::from_utf8_lossy(path_bytes).into_owned();
+            cursor += path_len;
+
+            let offset = read_u64(&contents, &mut cursor)?;
+            let compressed_len = read_u32(&contents, &mut cursor)?;
+            let uncompressed_len = read_u32(&contents, &mut cursor)?;
+
+            index.push(IndexEntry {
+                path,
+                offset,
+                compressed_len,
+                uncompressed_len,
+            });
+        }
+
+        let payload = &contents[cursor..];
+        let mut entries = BTreeMap::new();
+        for entry in index {
+            let start = entry.offset as usize;
+            let end = start + entry.compressed_len as usize;
+            let compressed = payload.get(start..end).ok_or_else(truncated)?;
+            let decompressed = zstd::decode_all(compressed)?;
+            debug_assert_eq!(decompressed.len(), entry.uncompressed_len as usize);
+            entries.insert(entry.path, decompressed);
+        }
+
+        Ok(Self { entries })
+    }
+
+    fn read_to_string(&self, path: &str) -> Result<String> {
+        self.entries
+            .get(path)
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+            .ok_or(Error::NotSupported {
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            })
+    }
+
+    fn read_u32(&self, path: &str) -> Result<u32> {
+        decode::parse_u32(&self.read_to_string(path)?)
+    }
+
+    fn read_bit(&self, path: &str) -> Result<bool> {
+        decode::parse_bit(&self.read_to_string(path)?)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        let prefix = format!("{path}/");
+        self.entries.contains_key(path) || self.entries.keys().any(|p| p.starts_with(&prefix))
+    }
+
+    /// The immediate child names of `path`, mirroring a single-level
+    /// directory read against the captured tree.
+    fn child_names(&self, path: &str) -> Vec<String> {
+        let prefix = format!("{path}/");
+        let mut names: Vec<String> = self
+            .entries
+            .keys()
+            .filter_map(|p| p.strip_prefix(&prefix))
+            .filter_map(|rest| rest.split('/').next())
+            .map(str::to_string)
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+fn truncated() -> Error {
+    Error::ParseStringError {
+        field: "snapshot archive".to_string(),
+        value: "truncated".to_string(),
+        #[cfg(feature = "backtrace")]
+        backtrace: crate::backtrace::Backtrace::capture(),
+    }
+}
+
+fn read_u32(buf: &[u8], cursor: &mut usize) -> Result<u32> {
+    let bytes: [u8; 4] = buf.get(*cursor..*cursor + 4).ok_or_else(truncated)?.try_into().unwrap();
+    *cursor += 4;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(buf: &[u8], cursor: &mut usize) -> Result<u64> {
+    let bytes: [u8; 8] = buf.get(*cursor..*cursor + 8).ok_or_else(truncated)?.try_into().unwrap();
+    *cursor += 8;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Replays a captured [`Snapshot`] through the [`OsBackend`] trait, for
+/// hardware-free regression tests and "attach this file to the bug"
+/// debugging.
+pub struct ReplayBackend {
+    snapshot: Snapshot,
+}
+
+impl ReplayBackend {
+    pub fn new(snapshot: Snapshot) -> Self {
+        Self { snapshot }
+    }
+
+    /// Loads an archive written by [`Snapshot::save`] and replays it.
+    pub fn from_archive(path: &Path) -> Result<Self> {
+        Ok(Self::new(Snapshot::load(path)?))
+    }
+
+    /// The archive-backed counterpart to
+    /// [`super::sysfs::sysfs_reader::SysfsReader::discover_identity`]:
+    /// decodes the Discover Identity ACK's VDOs out of the captured
+    /// `identity` attribute directory instead of reading them live.
+    fn discover_identity(&self, connector_nr: usize, recipient: PdMessageRecipient) -> Result<Pd3p2DiscoverIdentityResponse> {
+        let path_str = match recipient {
+            PdMessageRecipient::Sop => format!("{SYSFS_TYPEC_PATH}/port{connector_nr}-partner/identity"),
+            PdMessageRecipient::SopPrime => format!("{SYSFS_TYPEC_PATH}/port{connector_nr}-cable/identity"),
+            _ => {
+                return Err(Error::NotSupported {
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                })
+            }
+        };
+
+        let cert_stat = self.snapshot.read_u32(&format!("{path_str}/cert_stat"))?;
+        let id_header = self.snapshot.read_u32(&format!("{path_str}/id_header"))?;
+        let product = self.snapshot.read_u32(&format!("{path_str}/product"))?;
+
+        let mut product_type_vdo = [
+            Pd3p2ProductTypeVdo::default(),
+            Pd3p2ProductTypeVdo::default(),
+            Pd3p2ProductTypeVdo::default(),
+        ];
+        for (i, vdo) in product_type_vdo.iter_mut().enumerate() {
+            let value = self.snapshot.read_u32(&format!("{path_str}/product_type_vdo{}", i + 1))?;
+            if value != 0 {
+                *vdo = Pd3p2ProductTypeVdo::n(value).ok_or(Error::ParseError {
+                    field: "product_type_vdo".to_string(),
+                    value,
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                })?;
+            }
+        }
+
+        let binding = id_header.to_le_bytes();
+        let mut br = BitReader::new(std::io::Cursor::new(&binding));
+        let id_header_vdo = Pd3p2IdHeaderVdo::from_bytes(&mut br)?;
+
+        let binding = cert_stat.to_le_bytes();
+        let mut br = BitReader::new(std::io::Cursor::new(&binding));
+        let cert_stat = Pd3p2CertStatVdo::from_bytes(&mut br)?;
+
+        let binding = product.to_le_bytes();
+        let mut br = BitReader::new(std::io::Cursor::new(&binding));
+        let product_vdo = Pd3p2ProductVdo::from_bytes(&mut br)?;
+
+        Ok(Pd3p2DiscoverIdentityResponse {
+            header: Default::default(),
+            id_header_vdo,
+            cert_stat,
+            product_vdo,
+            product_type_vdo,
+        })
+    }
+
+    /// The archive-backed counterpart to
+    /// [`super::sysfs::sysfs_reader::SysfsReader::read_fixed_supply_pdo`].
+    fn read_fixed_supply_pdo(&self, path: &str, src_or_sink: GetPdosSrcOrSink) -> Result<Pd3p2FixedSupplyPdo> {
+        let dual_role_power = self.snapshot.read_bit(&format!("{path}/dual_role_power"))?;
+        let higher_capability = self.snapshot.read_bit(&format!("{path}/higher_capability"))?;
+        let unconstrained_power = self.snapshot.read_bit(&format!("{path}/unconstrained_power"))?;
+        let usb_communications_capable = self.snapshot.read_bit(&format!("{path}/usb_communication_capable"))?;
+        let dual_role_data = self.snapshot.read_bit(&format!("{path}/dual_role_data"))?;
+
+        let (fast_role_swap_attr, current_attr) = match src_or_sink {
+            GetPdosSrcOrSink::Source => ("fast_role_swap", "maximum_current"),
+            GetPdosSrcOrSink::Sink => ("fast_role_swap_current", "operational_current"),
+        };
+        let fast_role_swap_raw = self.snapshot.read_u32(&format!("{path}/{fast_role_swap_attr}"))?;
+        let fast_role_swap = Pd3p2FastRoleSwap::n(fast_role_swap_raw).ok_or_else(|| Error::ParseError {
+            field: "fast_role_swap".into(),
+            value: fast_role_swap_raw,
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })?;
+        let voltage = (self.snapshot.read_u32(&format!("{path}/voltage"))? / 50).into();
+        let operational_current = (self.snapshot.read_u32(&format!("{path}/{current_attr}"))? / 10).into();
+
+        Ok(Pd3p2FixedSupplyPdo {
+            dual_role_power,
+            higher_capability,
+            unconstrained_power,
+            usb_communications_capable,
+            dual_role_data,
+            fast_role_swap,
+            voltage,
+            operational_current,
+        })
+    }
+
+    /// The archive-backed counterpart to
+    /// [`super::sysfs::sysfs_reader::SysfsReader::read_variable_supply_pdo`].
+    fn read_variable_supply_pdo(&self, path: &str) -> Result<Pd3p2VariableSupplyPdo> {
+        let max_voltage = (self.snapshot.read_u32(&format!("{path}/maximum_voltage"))? / 100).into();
+        let min_voltage = (self.snapshot.read_u32(&format!("{path}/minimum_voltage"))? / 100).into();
+        let max_current = (self.snapshot.read_u32(&format!("{path}/maximum_current"))? / 50).into();
+
+        Ok(Pd3p2VariableSupplyPdo {
+            max_voltage,
+            min_voltage,
+            max_current,
+        })
+    }
+
+    /// The archive-backed counterpart to
+    /// [`super::sysfs::sysfs_reader::SysfsReader::read_battery_supply_pdo`].
+    fn read_battery_supply_pdo(&self, path: &str, src_or_sink: GetPdosSrcOrSink) -> Result<Pd3p2BatterySupplyPdo> {
+        let max_voltage = (self.snapshot.read_u32(&format!("{path}/maximum_voltage"))? / 50).into();
+        let min_voltage = (self.snapshot.read_u32(&format!("{path}/minimum_voltage"))? / 50).into();
+        let power_attr = match src_or_sink {
+            GetPdosSrcOrSink::Source => "maximum_power",
+            GetPdosSrcOrSink::Sink => "operational_power",
+        };
+        let operational_power = (self.snapshot.read_u32(&format!("{path}/{power_attr}"))? / 250).into();
+
+        Ok(Pd3p2BatterySupplyPdo {
+            max_voltage,
+            min_voltage,
+            operational_power,
+        })
+    }
+}
+
+impl OsBackend for ReplayBackend {
+    fn capabilities(&mut self) -> Result<UcsiCapability> {
+        let mut num_ports = 0;
+        let mut num_alt_modes = 0;
+        let mut pd_version = Default::default();
+        let mut usb_type_c_version = Default::default();
+
+        let port_re = Regex::new(r"^port\d+$").unwrap();
+        let alt_mode_re = Regex::new(r"^port\d\.\d$").unwrap();
+
+        for entry_name in self.snapshot.child_names(SYSFS_TYPEC_PATH) {
+            if !port_re.is_match(&entry_name) {
+                continue;
+            }
+            num_ports += 1;
+
+            let port_path = format!("{SYSFS_TYPEC_PATH}/{entry_name}");
+            for port_entry_name in self.snapshot.child_names(&port_path) {
+                if alt_mode_re.is_match(&port_entry_name) {
+                    num_alt_modes += 1;
+                }
+            }
+
+            let content = self
+                .snapshot
+                .read_to_string(&format!("{port_path}/usb_power_delivery_revision"))?;
+            pd_version = decode::parse_bcd(&content)?;
+
+            let content = self.snapshot.read_to_string(&format!("{port_path}/usb_typec_revision"))?;
+            usb_type_c_version = decode::parse_bcd(&content)?;
+        }
+
+        Ok(UcsiCapability {
+            num_connectors: num_ports,
+            num_alt_modes,
+            pd_version,
+            usb_type_c_version,
+            ..Default::default()
+        })
+    }
+
+    fn connector_capabilties(&mut self, connector_nr: usize) -> Result<UcsiConnectorCapability> {
+        let path_str = format!("{SYSFS_TYPEC_PATH}/port{connector_nr}");
+
+        let content = self.snapshot.read_to_string(&format!("{path_str}/power_role"))?;
+        let mut connector_capabilities = UcsiConnectorCapability {
+            operation_mode: decode::parse_opr(&content)?,
+            ..Default::default()
+        };
+
+        match connector_capabilities.operation_mode {
+            ConnectorCapabilityOperationMode::Drp => {
+                connector_capabilities.provider = true;
+                connector_capabilities.consumer = true;
+            }
+            ConnectorCapabilityOperationMode::RdOnly => {
+                connector_capabilities.consumer = true;
+            }
+            _ => {
+                connector_capabilities.provider = true;
+            }
+        }
+
+        if crate::is_chrome_os() {
+            if let Ok(content) = self
+                .snapshot
+                .read_to_string(&format!("{path_str}/port{connector_nr}-partner/usb_power_delivery_revision"))
+            {
+                connector_capabilities.partner_pd_revision = decode::parse_pd_revision(&content)?;
+            }
+        }
+
+        Ok(connector_capabilities)
+    }
+
+    fn alternate_modes(
+        &mut self,
+        recipient: GetAlternateModesRecipient,
+        connector_nr: usize,
+    ) -> Result<Vec<UcsiAlternateMode>> {
+        let mut alt_modes = vec![];
+
+        loop {
+            let num_alt_mode = alt_modes.len();
+            let path_str = match recipient {
+                GetAlternateModesRecipient::Connector => {
+                    format!("{SYSFS_TYPEC_PATH}/port{connector_nr}/port{connector_nr}.{num_alt_mode}")
+                }
+                GetAlternateModesRecipient::Sop => {
+                    format!(
+                        "{SYSFS_TYPEC_PATH}/port{connector_nr}/port{connector_nr}-partner/port{connector_nr}-partner.{num_alt_mode}"
+                    )
+                }
+                GetAlternateModesRecipient::SopPrime => {
+                    format!(
+                        "{SYSFS_TYPEC_PATH}/port{connector_nr}-cable/port{connector_nr}-plug0/port{connector_nr}-plug0.{num_alt_mode}"
+                    )
+                }
+                _ => {
+                    return Err(Error::NotSupported {
+                        #[cfg(feature = "backtrace")]
+                        backtrace: crate::backtrace::Backtrace::capture(),
+                    })
+                }
+            };
+
+            let mut alt_mode = UcsiAlternateMode::default();
+
+            let svid = match self.snapshot.read_to_string(&format!("{path_str}/svid")) {
+                Ok(content) => content,
+                Err(_) => break,
+            };
+            alt_mode.svid[0] = decode::parse_hex_u32(&svid)?;
+
+            let vdo = match self.snapshot.read_to_string(&format!("{path_str}/vdo")) {
+                Ok(content) => content,
+                Err(_) => break,
+            };
+            alt_mode.vdo[0] = decode::parse_hex_u32(&vdo)?;
+
+            alt_modes.push(alt_mode);
+        }
+
+        Ok(alt_modes)
+    }
+
+    fn cable_properties(&mut self, connector_nr: usize) -> Result<UcsiCableProperty> {
+        let path_str = format!("{SYSFS_TYPEC_PATH}/port{connector_nr}-cable");
+
+        let content = self.snapshot.read_to_string(&format!("{path_str}/plug_type"))?;
+        let plug_end_type = decode::parse_cable_plug_type(&content)?;
+
+        let content = self.snapshot.read_to_string(&format!("{path_str}/type"))?;
+        let cable_type = decode::parse_cable_type(&content)?;
+
+        let content = self
+            .snapshot
+            .read_to_string(&format!("{SYSFS_TYPEC_PATH}/port{connector_nr}-plug0/number_of_alternate_modes"))?;
+        let mode_support = decode::parse_cable_mode_support(&content)?;
+
+        Ok(UcsiCableProperty {
+            plug_end_type,
+            cable_type,
+            mode_support,
+            ..Default::default()
+        })
+    }
+
+    fn cable_identity(&mut self, connector_nr: usize) -> Result<crate::vdo::CableIdentity> {
+        let identity = self.discover_identity(connector_nr, PdMessageRecipient::SopPrime)?;
+
+        let path_str = format!("{SYSFS_TYPEC_PATH}/port{connector_nr}-cable/identity");
+        let cable_vdo_bits = self.snapshot.read_u32(&format!("{path_str}/product_type_vdo1"))?;
+        let binding = cable_vdo_bits.to_le_bytes();
+        let mut br = BitReader::new(std::io::Cursor::new(&binding));
+        let cable_vdo = crate::vdo::CableVdo::from_bytes(&mut br)?;
+
+        Ok(crate::vdo::CableIdentity { identity, cable_vdo })
+    }
+
+    fn connector_status(&mut self, connector_nr: usize) -> Result<UcsiConnectorStatus> {
+        let mut connector_status = UcsiConnectorStatus::default();
+
+        let partner_path_str = format!("{SYSFS_TYPEC_PATH}/port{connector_nr}/port{connector_nr}-partner");
+        connector_status.connect_status = self.snapshot.exists(&partner_path_str);
+
+        let psy_path_str = format!("{SYSFS_PSY_PATH}/ucsi-source-psy-USBC000:00{}", connector_nr + 1);
+
+        let content = self.snapshot.read_to_string(&format!("{psy_path_str}/online"))?;
+        if decode::parse_hex_u32(&content)? != 0 {
+            let content = self.snapshot.read_to_string(&format!("{psy_path_str}/current_now"))?;
+            let cur = decode::parse_u32(&content)? / 1000;
+
+            let content = self.snapshot.read_to_string(&format!("{psy_path_str}/voltage_now"))?;
+            let volt = decode::parse_u32(&content)? / 1000;
+
+            let op_mw = (cur * volt) / (250 * 1000);
+
+            let content = self.snapshot.read_to_string(&format!("{psy_path_str}/current_max"))?;
+            let cur = decode::parse_u32(&content)? / 1000;
+
+            let content = self.snapshot.read_to_string(&format!("{psy_path_str}/voltage_max"))?;
+            let volt = decode::parse_u32(&content)? / 1000;
+
+            let max_mw = (cur * volt) / (250 * 1000);
+
+            connector_status.negotiated_power_level = (op_mw << 10) | (max_mw) & 0x3ff;
+        }
+
+        Ok(connector_status)
+    }
+
+    fn pd_message(
+        &mut self,
+        connector_nr: usize,
+        recipient: PdMessageRecipient,
+        response_type: PdMessageResponseType,
+    ) -> Result<PdMessage> {
+        match response_type {
+            PdMessageResponseType::DiscoverIdentity => Ok(PdMessage::Pd3p2DiscoverIdentityResponse(
+                self.discover_identity(connector_nr, recipient)?,
+            )),
+            // Everything else needs the raw message bytes `PdMessage::from_bytes`
+            // decodes; sysfs (and therefore this archive) only ever captured
+            // already-structured per-attribute files, not a byte stream, so
+            // there's nothing to decode here either. Mirrors
+            // `SysfsBackend::pd_message`'s scope.
+            _ => Err(Error::NotSupported {
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            }),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn pdos(
+        &mut self,
+        connector_nr: usize,
+        partner_pdo: bool,
+        _pdo_offset: u32,
+        _nr_pdos: usize,
+        src_or_sink_pdos: GetPdosSrcOrSink,
+        _pdo_type: GetPdoSourceCapabilitiesType,
+        _revision: BcdWrapper,
+    ) -> Result<Vec<PdPdo>> {
+        let root = if partner_pdo {
+            format!("{SYSFS_TYPEC_PATH}/port{connector_nr}-partner")
+        } else {
+            format!("{SYSFS_TYPEC_PATH}/port{connector_nr}")
+        };
+        let capabilities_dir = match src_or_sink_pdos {
+            GetPdosSrcOrSink::Source => format!("{root}/usb_power_delivery/source-capabilities"),
+            GetPdosSrcOrSink::Sink => format!("{root}/usb_power_delivery/sink-capabilities"),
+        };
+
+        let mut pdos = Vec::new();
+        for entry_name in self.snapshot.child_names(&capabilities_dir) {
+            let pdo_path = format!("{capabilities_dir}/{entry_name}");
+
+            let pdo = if entry_name.contains("fixed") {
+                PdPdo::Pd3p2FixedSupplyPdo(self.read_fixed_supply_pdo(&pdo_path, src_or_sink_pdos)?)
+            } else if entry_name.contains("variable") {
+                PdPdo::Pd3p2VariableSupplyPdo(self.read_variable_supply_pdo(&pdo_path)?)
+            } else if entry_name.contains("battery") {
+                PdPdo::Pd3p2BatterySupplyPdo(self.read_battery_supply_pdo(&pdo_path, src_or_sink_pdos)?)
+            } else {
+                continue;
+            };
+
+            pdos.push(pdo);
+        }
+
+        Ok(pdos)
+    }
+
+    fn set_power_role(&mut self, _connector_nr: usize, _role: crate::ucsi::PowerRole) -> Result<()> {
+        Err(Error::NotSupported {
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })
+    }
+
+    fn set_data_role(&mut self, _connector_nr: usize, _role: crate::ucsi::DataRole) -> Result<()> {
+        Err(Error::NotSupported {
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })
+    }
+
+    fn set_usb_operation_mode(
+        &mut self,
+        _connector_nr: usize,
+        _mode: ConnectorCapabilityOperationMode,
+    ) -> Result<()> {
+        Err(Error::NotSupported {
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })
+    }
+
+    fn set_alternate_mode(&mut self, _connector_nr: usize, _alt_mode_nr: usize, _enter: bool) -> Result<()> {
+        Err(Error::NotSupported {
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })
+    }
+
+    fn connector_reset(&mut self, _connector_nr: usize, _hard_reset: bool) -> Result<()> {
+        Err(Error::NotSupported {
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_snapshot() -> Snapshot {
+        let mut entries = BTreeMap::new();
+        entries.insert(
+            format!("{SYSFS_TYPEC_PATH}/port0/power_role"),
+            b"[source] sink".to_vec(),
+        );
+        Snapshot { entries }
+    }
+
+    #[test]
+    fn round_trips_through_an_archive() {
+        let snapshot = sample_snapshot();
+        let path = std::env::temp_dir().join("libtypec_rs_snapshot_test.tcsnap");
+        snapshot.save(&path).unwrap();
+
+        let loaded = Snapshot::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            loaded.read_to_string(&format!("{SYSFS_TYPEC_PATH}/port0/power_role")).unwrap(),
+            "[source] sink"
+        );
+    }
+
+    #[test]
+    fn replay_backend_reports_connector_capabilities() {
+        let snapshot = sample_snapshot();
+        let mut backend = ReplayBackend::new(snapshot);
+        let capabilities = backend.connector_capabilties(0).unwrap();
+        assert_eq!(capabilities.operation_mode, ConnectorCapabilityOperationMode::Drp);
+    }
+}
@@ -3,6 +3,11 @@
 // Ported from libtypec (Rajaram Regupathy <rajaram.regupathy@gmail.com>)
 
 //! The sysfs backend
+//!
+//! `SysfsReader` implements [`crate::source::TypecSource`], the keyed
+//! accessor trait the PD/VDO decode helpers are written against. A future
+//! embedded backend with no sysfs (and no `std`) could implement the same
+//! trait directly against a register map, reusing that decode logic as-is.
 
 use mockall_double::double;
 use regex::Regex;
@@ -24,7 +29,10 @@ use crate::ucsi::UcsiCapability;
 use crate::ucsi::UcsiConnectorCapability;
 use crate::ucsi::UcsiConnectorStatus;
 use crate::BcdWrapper;
+use crate::BitReader;
+use crate::Context;
 use crate::Error;
+use crate::FromBytes;
 use crate::OsBackend;
 use crate::Result;
 
@@ -33,8 +41,8 @@ use sysfs_reader::SysfsReader;
 #[double]
 use sysfs_walker::SysfsWalker;
 
-const SYSFS_TYPEC_PATH: &str = "/sys/class/typec";
-const SYSFS_PSY_PATH: &str = "/sys/class/power_supply";
+pub(crate) const SYSFS_TYPEC_PATH: &str = "/sys/class/typec";
+pub(crate) const SYSFS_PSY_PATH: &str = "/sys/class/power_supply";
 
 /// Creates a `PathBuf` from a string and returns an error if the path does not
 /// exist.
@@ -43,7 +51,7 @@ fn check_path(path: &str) -> Result<PathBuf> {
     if !path.exists() {
         Err(Error::NotSupported {
             #[cfg(feature = "backtrace")]
-            backtrace: std::backtrace::Backtrace::capture(),
+            backtrace: crate::backtrace::Backtrace::capture(),
         })
     } else {
         Ok(path)
@@ -56,15 +64,15 @@ pub mod sysfs_reader {
     #[cfg(test)]
     use mockall::{automock, predicate::*};
 
-    use std::io;
-    use std::io::Cursor;
     use std::path::Path;
     use std::path::PathBuf;
 
     use crate::pd::Pd3p2BatterySupplyPdo;
     use crate::pd::Pd3p2DiscoverIdentityResponse;
+    use crate::pd::Pd3p2EprAdjustableVoltageSupplyPdo;
     use crate::pd::Pd3p2FastRoleSwap;
     use crate::pd::Pd3p2FixedSupplyPdo;
+    use crate::pd::Pd3p2SprAdjustableVoltageSupplyPdo;
     use crate::pd::Pd3p2SprProgrammableSupplyPdo;
     use crate::pd::Pd3p2VariableSupplyPdo;
     use crate::ucsi::CablePropertyPlugEndType;
@@ -72,14 +80,9 @@ pub mod sysfs_reader {
     use crate::ucsi::ConnectorCapabilityOperationMode;
     use crate::ucsi::PdMessageRecipient;
     use crate::ucsi::PdoType;
-    use crate::vdo::Pd3p2CertStatVdo;
-    use crate::vdo::Pd3p2IdHeaderVdo;
     use crate::vdo::Pd3p2ProductTypeVdo;
-    use crate::vdo::Pd3p2ProductVdo;
     use crate::BcdWrapper;
-    use crate::BitReader;
     use crate::Error;
-    use crate::FromBytes;
     use crate::Result;
 
     use super::SYSFS_TYPEC_PATH;
@@ -104,144 +107,46 @@ pub mod sysfs_reader {
             Ok(string)
         }
 
+        pub fn write_str(&mut self, content: &str) -> Result<()> {
+            let path = self.0.take().expect("Path not set");
+            std::fs::write(path, content)?;
+            Ok(())
+        }
+
         pub fn read_bcd(&mut self) -> Result<BcdWrapper> {
-            let content = self.read_file()?;
-            let mut chars = content.chars();
-
-            let high = chars
-                .next()
-                .ok_or(io::Error::new(io::ErrorKind::InvalidData, "File is empty"))?;
-            let _ = chars.next().ok_or(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "File is too short",
-            ))?;
-
-            // Sometimes we get simply "2"
-            let low = chars.next().unwrap_or('0');
-
-            let high = high.to_digit(10).ok_or(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("Invalid digit: {high}"),
-            ))?;
-            let low = low.to_digit(10).ok_or(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("Invalid digit: {low}"),
-            ))?;
-
-            let bcd = (high << 8) | low;
-
-            Ok(BcdWrapper(bcd))
+            super::decode::parse_bcd(&self.read_file()?)
         }
 
         pub fn read_opr(&mut self) -> Result<ConnectorCapabilityOperationMode> {
-            let content = self.read_file()?;
-            if content.contains("source") {
-                if content.contains("sink") {
-                    Ok(ConnectorCapabilityOperationMode::Drp)
-                } else {
-                    Ok(ConnectorCapabilityOperationMode::RpOnly)
-                }
-            } else {
-                Ok(ConnectorCapabilityOperationMode::RdOnly)
-            }
+            super::decode::parse_opr(&self.read_file()?)
         }
 
         pub fn read_pd_revision(&mut self) -> Result<u8> {
-            let content = self.read_file()?;
-            let mut chars = content.chars();
-
-            let b0 = chars.next().ok_or(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "File is too short",
-            ))?;
-            let _ = chars.next().ok_or(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "File is too short",
-            ))?;
-            let b2 = chars.next().ok_or(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "File is too short",
-            ))?;
-
-            let rev = ((b0 as u8 - b'0' as u8) << 4) | (b2 as u8 - b'0' as u8);
-            Ok(rev)
+            super::decode::parse_pd_revision(&self.read_file()?)
         }
 
         pub fn read_hex_u32(&mut self) -> Result<u32> {
-            let content = self.read_file()?.replace("0x", "");
-            let hex = u32::from_str_radix(content.trim(), 16).map_err(|_| {
-                io::Error::new(io::ErrorKind::InvalidData, "Could not parse hex value")
-            })?;
-            Ok(hex)
+            super::decode::parse_hex_u32(&self.read_file()?)
         }
 
         pub fn read_u32(&mut self) -> Result<u32> {
-            let mut content = self.read_file()?;
-            content.retain(|c| c.is_ascii_digit());
-
-            let dword = content.trim().parse::<u32>().map_err(|_| {
-                io::Error::new(io::ErrorKind::InvalidData, "Could not parse u32 value")
-            })?;
-            Ok(dword)
+            super::decode::parse_u32(&self.read_file()?)
         }
 
         pub fn read_bit(&mut self) -> Result<bool> {
-            let content = self.read_file()?;
-            let bit = content.trim().parse::<bool>().map_err(|_| {
-                io::Error::new(io::ErrorKind::InvalidData, "Could not parse bool value")
-            })?;
-            Ok(bit)
+            super::decode::parse_bit(&self.read_file()?)
         }
 
         pub fn read_cable_plug_type(&mut self) -> Result<CablePropertyPlugEndType> {
-            let content = self.read_file()?;
-            let plug_type = if content.contains("type-c") {
-                CablePropertyPlugEndType::UsbTypeC
-            } else if content.contains("type-a") {
-                CablePropertyPlugEndType::UsbTypeA
-            } else if content.contains("type-b") {
-                CablePropertyPlugEndType::UsbTypeB
-            } else {
-                CablePropertyPlugEndType::OtherNotUsb
-            };
-
-            Ok(plug_type)
+            super::decode::parse_cable_plug_type(&self.read_file()?)
         }
 
         pub fn read_cable_type(&mut self) -> Result<CablePropertyType> {
-            let content = self.read_file()?;
-            let cable_type = if content.contains("active") {
-                CablePropertyType::Active
-            } else if content.contains("passive") {
-                CablePropertyType::Passive
-            } else {
-                return Err(Error::ParseStringError {
-                    field: "cable_type".to_string(),
-                    value: content,
-                    #[cfg(feature = "backtrace")]
-                    backtrace: std::backtrace::Backtrace::capture(),
-                });
-            };
-
-            Ok(cable_type)
+            super::decode::parse_cable_type(&self.read_file()?)
         }
 
         pub fn read_cable_mode_support(&mut self) -> Result<bool> {
-            let content = self.read_file()?;
-            let mode_support = match content.chars().next() {
-                Some('0') => false,
-                Some(_) => true,
-                None => {
-                    return Err(Error::ParseStringError {
-                        field: "cable_mode_support".to_string(),
-                        value: content,
-                        #[cfg(feature = "backtrace")]
-                        backtrace: std::backtrace::Backtrace::capture(),
-                    });
-                }
-            };
-
-            Ok(mode_support)
+            super::decode::parse_cable_mode_support(&self.read_file()?)
         }
 
         pub fn read_fixed_supply_pdo(
@@ -268,7 +173,7 @@ pub mod sysfs_reader {
                             field: "fast_role_swap".into(),
                             value: fast_role_swap,
                             #[cfg(feature = "backtrace")]
-                            backtrace: std::backtrace::Backtrace::capture(),
+                            backtrace: crate::backtrace::Backtrace::capture(),
                         })?;
                     self.set_path(&path.join("voltage").to_string_lossy())?;
                     let voltage = (self.read_u32()? / 50).into();
@@ -304,7 +209,7 @@ pub mod sysfs_reader {
                             field: "fast_role_swap".into(),
                             value: fast_role_swap,
                             #[cfg(feature = "backtrace")]
-                            backtrace: std::backtrace::Backtrace::capture(),
+                            backtrace: crate::backtrace::Backtrace::capture(),
                         })?;
                     self.set_path(&path.join("voltage").to_string_lossy())?;
                     let voltage = (self.read_u32()? / 50).into();
@@ -353,6 +258,45 @@ pub mod sysfs_reader {
             })
         }
 
+        pub fn read_spr_avs_supply_pdo(
+            &mut self,
+            path: &Path,
+        ) -> Result<Pd3p2SprAdjustableVoltageSupplyPdo> {
+            self.set_path(&path.join("peak_current").to_string_lossy())?;
+            let peak_current = self.read_u32()? as u8;
+            self.set_path(&path.join("maximum_voltage").to_string_lossy())?;
+            let max_voltage = (self.read_u32()? / 100).into();
+            self.set_path(&path.join("minimum_voltage").to_string_lossy())?;
+            let min_voltage = (self.read_u32()? / 100).into();
+
+            Ok(Pd3p2SprAdjustableVoltageSupplyPdo {
+                peak_current,
+                max_voltage,
+                min_voltage,
+            })
+        }
+
+        pub fn read_epr_avs_supply_pdo(
+            &mut self,
+            path: &Path,
+        ) -> Result<Pd3p2EprAdjustableVoltageSupplyPdo> {
+            self.set_path(&path.join("pdp").to_string_lossy())?;
+            let pdp = (self.read_u32()? * 1000).into();
+            self.set_path(&path.join("peak_current").to_string_lossy())?;
+            let peak_current = self.read_u32()? as u8;
+            self.set_path(&path.join("maximum_voltage").to_string_lossy())?;
+            let max_voltage = (self.read_u32()? / 100).into();
+            self.set_path(&path.join("minimum_voltage").to_string_lossy())?;
+            let min_voltage = (self.read_u32()? / 100).into();
+
+            Ok(Pd3p2EprAdjustableVoltageSupplyPdo {
+                pdp,
+                peak_current,
+                max_voltage,
+                min_voltage,
+            })
+        }
+
         pub fn read_battery_supply_pdo(
             &mut self,
             path: &Path,
@@ -405,43 +349,25 @@ pub mod sysfs_reader {
             conn_num: usize,
             recipient: PdMessageRecipient,
         ) -> Result<Pd3p2DiscoverIdentityResponse> {
-            let (cert_stat, id_header, product, product_type_vdo) = match recipient {
+            let path_str = match recipient {
                 PdMessageRecipient::Sop => {
-                    let path_str =
-                        format!("{}/port{}-partner/identity", SYSFS_TYPEC_PATH, conn_num);
-                    self.read_identity(&path_str)?
+                    format!("{}/port{}-partner/identity", SYSFS_TYPEC_PATH, conn_num)
                 }
                 PdMessageRecipient::SopPrime => {
-                    let path_str = format!("{}/port{}-cable/identity", SYSFS_TYPEC_PATH, conn_num);
-                    self.read_identity(&path_str)?
+                    format!("{}/port{}-cable/identity", SYSFS_TYPEC_PATH, conn_num)
                 }
                 _ => {
                     return Err(Error::NotSupported {
                         #[cfg(feature = "backtrace")]
-                        backtrace: std::backtrace::Backtrace::capture(),
+                        backtrace: crate::backtrace::Backtrace::capture(),
                     })
                 }
             };
 
-            let binding = id_header.to_le_bytes();
-            let mut br = BitReader::new(Cursor::new(&binding));
-            let id_header_vdo = Pd3p2IdHeaderVdo::from_bytes(&mut br)?;
-
-            let binding = cert_stat.to_le_bytes();
-            let mut br = BitReader::new(Cursor::new(&binding));
-            let cert_stat = Pd3p2CertStatVdo::from_bytes(&mut br)?;
-
-            let binding = product.to_le_bytes();
-            let mut br = BitReader::new(Cursor::new(&binding));
-            let product_vdo = Pd3p2ProductVdo::from_bytes(&mut br)?;
-
-            Ok(Pd3p2DiscoverIdentityResponse {
-                header: Default::default(),
-                id_header_vdo,
-                cert_stat,
-                product_vdo,
-                product_type_vdo,
-            })
+            // The actual decode (cert stat/ID header/product VDOs) lives in
+            // `crate::source::discover_identity`, generic over
+            // `TypecSource`, so it isn't tied to sysfs being the source.
+            crate::source::discover_identity(self, &path_str)
         }
 
         fn read_identity(
@@ -467,13 +393,34 @@ pub mod sysfs_reader {
                         field: "product_type_vdo".to_string(),
                         value,
                         #[cfg(feature = "backtrace")]
-                        backtrace: std::backtrace::Backtrace::capture(),
+                        backtrace: crate::backtrace::Backtrace::capture(),
                     })?;
                 }
             }
             Ok((cert_stat, id_header, product, product_type_vdo))
         }
     }
+
+    impl crate::source::TypecSource for SysfsReader {
+        fn read_u32(&mut self, key: &str) -> Result<u32> {
+            self.set_path(key)?;
+            self.read_u32()
+        }
+
+        fn read_bit(&mut self, key: &str) -> Result<bool> {
+            self.set_path(key)?;
+            self.read_bit()
+        }
+
+        fn read_bcd(&mut self, key: &str) -> Result<BcdWrapper> {
+            self.set_path(key)?;
+            self.read_bcd()
+        }
+
+        fn read_identity(&mut self, key: &str) -> Result<(u32, u32, u32, [Pd3p2ProductTypeVdo; 3])> {
+            self.read_identity(key)
+        }
+    }
 }
 
 /// A module to differentiate `SysfsWalker` from `MockSysfsWalker`. This is a
@@ -541,7 +488,7 @@ mod sysfs_walker {
                 res.map_err(|walkdir_error| Error::DirError {
                     source: Box::new(walkdir_error),
                     #[cfg(feature = "backtrace")]
-                    backtrace: std::backtrace::Backtrace::capture(),
+                    backtrace: crate::backtrace::Backtrace::capture(),
                 })
                 // Convert into a Box<dyn Entry>
                 .map(|dir_entry| Box::new(dir_entry) as Box<dyn Entry>)
@@ -566,7 +513,7 @@ impl SysfsBackend {
         if walker.iter().count() == 1 {
             return Err(Error::NotSupported {
                 #[cfg(feature = "backtrace")]
-                backtrace: std::backtrace::Backtrace::capture(),
+                backtrace: crate::backtrace::Backtrace::capture(),
             });
         }
 
@@ -575,6 +522,116 @@ impl SysfsBackend {
             walker: SysfsWalker::new()?,
         })
     }
+
+    /// Subscribes to kernel `typec`/`power_supply` uevents, returning a
+    /// [`super::uevent::ConnectorMonitor`] that yields typed connector
+    /// change notifications instead of requiring callers to re-poll
+    /// [`OsBackend::connector_status`] on a timer.
+    pub fn watch(&self) -> Result<super::uevent::ConnectorMonitor> {
+        super::uevent::ConnectorMonitor::new()
+    }
+
+    /// Reads the `power_role` attribute's enumerated choices to determine
+    /// which [`ConnectorCapabilityOperationMode`] `connector_nr` is
+    /// currently operating in, without changing anything. Used to validate
+    /// role-swap requests before they're written.
+    fn power_operation_mode(&mut self, connector_nr: usize) -> Result<ConnectorCapabilityOperationMode> {
+        self.reader
+            .set_path(&format!("{SYSFS_TYPEC_PATH}/port{connector_nr}/power_role"))?;
+        self.reader.read_opr()
+    }
+
+    /// Writes `content` to `connector_nr`'s `attribute` file, then reads
+    /// back [`UcsiConnectorStatus`] so the caller can confirm the write
+    /// actually took effect. Mirrors how a UCSI PPM issues a control
+    /// command and then polls `GET_CONNECTOR_STATUS` for the result.
+    fn apply_role_write(
+        &mut self,
+        connector_nr: usize,
+        attribute: &str,
+        content: &str,
+    ) -> Result<UcsiConnectorStatus> {
+        self.reader
+            .set_path(&format!("{SYSFS_TYPEC_PATH}/port{connector_nr}/{attribute}"))?;
+        self.reader.write_str(content)?;
+        self.connector_status(connector_nr)
+    }
+
+    /// Requests a power role swap on `connector_nr`, validating it against
+    /// the connector's currently declared
+    /// [`ConnectorCapabilityOperationMode`] (read fresh via `power_role`)
+    /// before writing, and returns the resulting [`UcsiConnectorStatus`] so
+    /// callers can confirm the swap without a separate
+    /// [`OsBackend::connector_status`] call. Returns
+    /// [`Error::NotSupported`] if `role` isn't compatible with the
+    /// connector's operation mode, or if the `power_role` attribute is
+    /// absent.
+    pub fn swap_power_role(&mut self, connector_nr: usize, role: crate::ucsi::PowerRole) -> Result<UcsiConnectorStatus> {
+        let allowed = match self.power_operation_mode(connector_nr)? {
+            ConnectorCapabilityOperationMode::Drp => true,
+            ConnectorCapabilityOperationMode::RpOnly => role == crate::ucsi::PowerRole::Source,
+            ConnectorCapabilityOperationMode::RdOnly => role == crate::ucsi::PowerRole::Sink,
+            _ => false,
+        };
+        if !allowed {
+            return Err(Error::NotSupported {
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            });
+        }
+
+        let content = match role {
+            crate::ucsi::PowerRole::Source => "source",
+            crate::ucsi::PowerRole::Sink => "sink",
+        };
+        self.apply_role_write(connector_nr, "power_role", content)
+    }
+
+    /// Requests a data role swap on `connector_nr`, validating it against
+    /// the connector's declared capabilities before writing, and returns
+    /// the resulting [`UcsiConnectorStatus`] so callers can confirm the
+    /// swap. Returns [`Error::NotSupported`] if `role` isn't swappable, or
+    /// if the `data_role` attribute is absent.
+    pub fn swap_data_role(&mut self, connector_nr: usize, role: crate::ucsi::DataRole) -> Result<UcsiConnectorStatus> {
+        let capabilities = self.connector_capabilties(connector_nr)?;
+        let allowed = match role {
+            crate::ucsi::DataRole::Host => capabilities.swap_to_dfp,
+            crate::ucsi::DataRole::Device => capabilities.swap_to_ufp,
+        };
+        if !allowed {
+            return Err(Error::NotSupported {
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            });
+        }
+
+        let content = match role {
+            crate::ucsi::DataRole::Host => "host",
+            crate::ucsi::DataRole::Device => "device",
+        };
+        self.apply_role_write(connector_nr, "data_role", content)
+    }
+
+    /// Sets which power role `connector_nr` should prefer when negotiating
+    /// as a dual-role port, and returns the resulting
+    /// [`UcsiConnectorStatus`]. Only meaningful for connectors currently
+    /// operating in [`ConnectorCapabilityOperationMode::Drp`]; returns
+    /// [`Error::NotSupported`] otherwise, or if the `preferred_role`
+    /// attribute is absent.
+    pub fn set_preferred_role(&mut self, connector_nr: usize, role: crate::ucsi::PowerRole) -> Result<UcsiConnectorStatus> {
+        if self.power_operation_mode(connector_nr)? != ConnectorCapabilityOperationMode::Drp {
+            return Err(Error::NotSupported {
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            });
+        }
+
+        let content = match role {
+            crate::ucsi::PowerRole::Source => "source",
+            crate::ucsi::PowerRole::Sink => "sink",
+        };
+        self.apply_role_write(connector_nr, "preferred_role", content)
+    }
 }
 
 impl OsBackend for SysfsBackend {
@@ -697,7 +754,7 @@ impl OsBackend for SysfsBackend {
                 _ => {
                     return Err(Error::NotSupported {
                         #[cfg(feature = "backtrace")]
-                        backtrace: std::backtrace::Backtrace::capture(),
+                        backtrace: crate::backtrace::Backtrace::capture(),
                     })
                 }
             };
@@ -745,6 +802,22 @@ impl OsBackend for SysfsBackend {
         Ok(cable_property)
     }
 
+    fn cable_identity(&mut self, connector_nr: usize) -> Result<crate::vdo::CableIdentity> {
+        let identity = self
+            .reader
+            .discover_identity(connector_nr, PdMessageRecipient::SopPrime)?;
+
+        let path_str = format!("{SYSFS_TYPEC_PATH}/port{connector_nr}-cable/identity");
+        self.reader
+            .set_path(&format!("{path_str}/product_type_vdo1"))?;
+        let cable_vdo_bits = self.reader.read_u32()?;
+        let binding = cable_vdo_bits.to_le_bytes();
+        let mut br = BitReader::new(std::io::Cursor::new(&binding));
+        let cable_vdo = crate::vdo::CableVdo::from_bytes(&mut br)?;
+
+        Ok(crate::vdo::CableIdentity { identity, cable_vdo })
+    }
+
     fn connector_status(&mut self, connector_nr: usize) -> Result<UcsiConnectorStatus> {
         let mut connector_status = UcsiConnectorStatus::default();
 
@@ -761,27 +834,48 @@ impl OsBackend for SysfsBackend {
         );
 
         let online_path = format!("{}/{}", psy_path_str, "online");
-        self.reader.set_path(&online_path)?;
-        let ret = self.reader.read_hex_u32()?;
+        self.reader
+            .set_path(&online_path)
+            .with_context(|| format!("getting connector {connector_nr} status: opening {online_path}"))?;
+        let ret = self
+            .reader
+            .read_hex_u32()
+            .with_context(|| format!("getting connector {connector_nr} status: reading {online_path}"))?;
 
         if ret != 0 {
             let current_now_path = format!("{}/{}", psy_path_str, "current_now");
             self.reader.set_path(&current_now_path)?;
-            let cur = self.reader.read_u32()? / 1000;
+            let cur = self
+                .reader
+                .read_u32()
+                .with_context(|| format!("getting connector {connector_nr} status: reading {current_now_path}"))?
+                / 1000;
 
             let voltage_now_path = format!("{}/{}", psy_path_str, "voltage_now");
             self.reader.set_path(&voltage_now_path)?;
-            let volt = self.reader.read_u32()? / 1000;
+            let volt = self
+                .reader
+                .read_u32()
+                .with_context(|| format!("getting connector {connector_nr} status: reading {voltage_now_path}"))?
+                / 1000;
 
             let op_mw = (cur * volt) / (250 * 1000);
 
             let current_max_path = format!("{}/{}", psy_path_str, "current_max");
             self.reader.set_path(&current_max_path)?;
-            let cur = self.reader.read_u32()? / 1000;
+            let cur = self
+                .reader
+                .read_u32()
+                .with_context(|| format!("getting connector {connector_nr} status: reading {current_max_path}"))?
+                / 1000;
 
             let voltage_max_path = format!("{}/{}", psy_path_str, "voltage_max");
             self.reader.set_path(&voltage_max_path)?;
-            let volt = self.reader.read_u32()? / 1000;
+            let volt = self
+                .reader
+                .read_u32()
+                .with_context(|| format!("getting connector {connector_nr} status: reading {voltage_max_path}"))?
+                / 1000;
 
             let max_mw = (cur * volt) / (250 * 1000);
 
@@ -803,9 +897,17 @@ impl OsBackend for SysfsBackend {
                     self.reader.discover_identity(connector_nr, recipient)?,
                 ))
             }
+            // Everything else (Control messages, Request, Vendor_Defined,
+            // and the remaining Extended messages) requires the raw message
+            // bytes `PdMessage::from_bytes` decodes. The `typec` sysfs class
+            // doesn't capture a byte stream anywhere, only already-structured
+            // per-attribute files, so there is no source to route through
+            // `PdMessage::from_bytes` here: this is a permanent limit of the
+            // sysfs backend, not a pending TODO. A raw-UCSI backend, which
+            // does see the actual message bytes, is what would decode these.
             _ => Err(Error::NotSupported {
                 #[cfg(feature = "backtrace")]
-                backtrace: std::backtrace::Backtrace::capture(),
+                backtrace: crate::backtrace::Backtrace::capture(),
             }),
         }
     }
@@ -872,6 +974,10 @@ impl OsBackend for SysfsBackend {
                 PdPdo::Pd3p2BatterySupplyPdo(
                     self.reader.read_battery_supply_pdo(port_path, pdo_type)?,
                 )
+            } else if entry_name.contains("epr_avs") {
+                PdPdo::Pd3p2EprAvsPdo(self.reader.read_epr_avs_supply_pdo(port_path)?)
+            } else if entry_name.contains("spr_avs") || entry_name.contains("avs") {
+                PdPdo::Pd3p2SprAvsPdo(self.reader.read_spr_avs_supply_pdo(port_path)?)
             } else if entry_name.contains("programmable") {
                 PdPdo::Pd3p2AugmentedPdo(
                     self.reader
@@ -886,6 +992,61 @@ impl OsBackend for SysfsBackend {
 
         Ok(pdos)
     }
+
+    fn set_power_role(&mut self, connector_nr: usize, role: crate::ucsi::PowerRole) -> Result<()> {
+        self.swap_power_role(connector_nr, role).map(|_| ())
+    }
+
+    fn set_data_role(&mut self, connector_nr: usize, role: crate::ucsi::DataRole) -> Result<()> {
+        self.swap_data_role(connector_nr, role).map(|_| ())
+    }
+
+    fn set_usb_operation_mode(
+        &mut self,
+        connector_nr: usize,
+        mode: ConnectorCapabilityOperationMode,
+    ) -> Result<()> {
+        let capabilities = self.connector_capabilties(connector_nr)?;
+        if capabilities.operation_mode != ConnectorCapabilityOperationMode::Drp && capabilities.operation_mode != mode
+        {
+            return Err(Error::NotSupported {
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            });
+        }
+
+        let content = match mode {
+            ConnectorCapabilityOperationMode::Drp => "dual",
+            ConnectorCapabilityOperationMode::RpOnly => "source",
+            ConnectorCapabilityOperationMode::RdOnly => "sink",
+            _ => {
+                return Err(Error::NotSupported {
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                })
+            }
+        };
+        self.reader
+            .set_path(&format!("{SYSFS_TYPEC_PATH}/port{connector_nr}/port_type"))?;
+        self.reader.write_str(content)
+    }
+
+    fn set_alternate_mode(&mut self, connector_nr: usize, alt_mode_nr: usize, enter: bool) -> Result<()> {
+        self.reader.set_path(&format!(
+            "{SYSFS_TYPEC_PATH}/port{connector_nr}/port{connector_nr}.{alt_mode_nr}/active"
+        ))?;
+        self.reader.write_str(if enter { "1" } else { "0" })
+    }
+
+    fn connector_reset(&mut self, _connector_nr: usize, _hard_reset: bool) -> Result<()> {
+        // The `typec` sysfs class has no attribute for triggering a PD
+        // reset; that requires raw UCSI command dispatch, which only the
+        // (not yet implemented) `linux-ucsi` backend can provide.
+        Err(Error::NotSupported {
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -1293,7 +1454,7 @@ mod tests {
             .return_once(|_| {
                 Err(Error::NotSupported {
                     #[cfg(feature = "backtrace")]
-                    backtrace: std::backtrace::Backtrace::capture(),
+                    backtrace: crate::backtrace::Backtrace::capture(),
                 })
             })
             .times(1)
@@ -0,0 +1,281 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! An async counterpart to the blocking [`super::sysfs::SysfsBackend`],
+//! backed by `tokio::fs` instead of `std::fs`. The two backends share the
+//! same UCSI decode helpers (see [`super::decode`]) so parsing a sysfs
+//! attribute's contents is never implemented twice; only the I/O (blocking
+//! vs. async) differs.
+
+use regex::Regex;
+
+use crate::pd::PdPdo;
+use crate::ucsi::ConnectorCapabilityOperationMode;
+use crate::ucsi::GetAlternateModesRecipient;
+use crate::ucsi::PdMessage;
+use crate::ucsi::PdMessageRecipient;
+use crate::ucsi::PdMessageResponseType;
+use crate::ucsi::UcsiAlternateMode;
+use crate::ucsi::UcsiCableProperty;
+use crate::ucsi::UcsiCapability;
+use crate::ucsi::UcsiConnectorCapability;
+use crate::ucsi::UcsiConnectorStatus;
+use crate::AsyncOsBackend;
+use crate::BcdWrapper;
+use crate::Error;
+use crate::Result;
+
+use super::decode;
+use super::sysfs::SYSFS_PSY_PATH;
+use super::sysfs::SYSFS_TYPEC_PATH;
+
+async fn read_to_string(path: &str) -> Result<String> {
+    if !tokio::fs::try_exists(path).await.unwrap_or(false) {
+        return Err(Error::NotSupported {
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        });
+    }
+
+    Ok(tokio::fs::read_to_string(path).await?)
+}
+
+async fn read_dir_names(path: &str) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    let mut entries = tokio::fs::read_dir(path).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        names.push(entry.file_name().to_string_lossy().into_owned());
+    }
+    Ok(names)
+}
+
+/// The async sysfs backend, backed by `tokio::fs`.
+#[derive(Default)]
+pub struct SysfsBackendAsync;
+
+impl SysfsBackendAsync {
+    /// Initializes the async sysfs backend.
+    pub async fn new() -> Result<Self> {
+        if !tokio::fs::try_exists(SYSFS_TYPEC_PATH).await.unwrap_or(false) {
+            return Err(Error::NotSupported {
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            });
+        }
+
+        Ok(Self)
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncOsBackend for SysfsBackendAsync {
+    async fn capabilities(&mut self) -> Result<UcsiCapability> {
+        let mut num_ports = 0;
+        let mut num_alt_modes = 0;
+        let mut pd_version = Default::default();
+        let mut usb_type_c_version = Default::default();
+
+        let port_re = Regex::new(r"^port\d+$").unwrap();
+        let alt_mode_re = Regex::new(r"^port\d\.\d$").unwrap();
+
+        for entry_name in read_dir_names(SYSFS_TYPEC_PATH).await? {
+            if !port_re.is_match(&entry_name) {
+                continue;
+            }
+            num_ports += 1;
+
+            let port_path = format!("{SYSFS_TYPEC_PATH}/{entry_name}");
+            for port_entry_name in read_dir_names(&port_path).await? {
+                if alt_mode_re.is_match(&port_entry_name) {
+                    num_alt_modes += 1;
+                }
+            }
+
+            let content = read_to_string(&format!("{port_path}/usb_power_delivery_revision")).await?;
+            pd_version = decode::parse_bcd(&content)?;
+
+            let content = read_to_string(&format!("{port_path}/usb_typec_revision")).await?;
+            usb_type_c_version = decode::parse_bcd(&content)?;
+        }
+
+        Ok(UcsiCapability {
+            num_connectors: num_ports,
+            num_alt_modes,
+            pd_version,
+            usb_type_c_version,
+            ..Default::default()
+        })
+    }
+
+    async fn connector_capabilties(&mut self, connector_nr: usize) -> Result<UcsiConnectorCapability> {
+        let path_str = format!("{SYSFS_TYPEC_PATH}/port{connector_nr}");
+
+        let content = read_to_string(&format!("{path_str}/power_role")).await?;
+        let mut connector_capabilities = UcsiConnectorCapability {
+            operation_mode: decode::parse_opr(&content)?,
+            ..Default::default()
+        };
+
+        match connector_capabilities.operation_mode {
+            ConnectorCapabilityOperationMode::Drp => {
+                connector_capabilities.provider = true;
+                connector_capabilities.consumer = true;
+            }
+            ConnectorCapabilityOperationMode::RdOnly => {
+                connector_capabilities.consumer = true;
+            }
+            _ => {
+                connector_capabilities.provider = true;
+            }
+        }
+
+        if crate::is_chrome_os() {
+            let content = read_to_string(&format!(
+                "{path_str}/port{connector_nr}-partner/usb_power_delivery_revision"
+            ))
+            .await?;
+            connector_capabilities.partner_pd_revision = decode::parse_pd_revision(&content)?;
+        }
+
+        Ok(connector_capabilities)
+    }
+
+    async fn alternate_modes(
+        &mut self,
+        recipient: GetAlternateModesRecipient,
+        connector_nr: usize,
+    ) -> Result<Vec<UcsiAlternateMode>> {
+        let mut alt_modes = vec![];
+
+        loop {
+            let num_alt_mode = alt_modes.len();
+            let path_str = match recipient {
+                GetAlternateModesRecipient::Connector => {
+                    format!("{SYSFS_TYPEC_PATH}/port{connector_nr}/port{connector_nr}.{num_alt_mode}")
+                }
+                GetAlternateModesRecipient::Sop => {
+                    format!(
+                        "{SYSFS_TYPEC_PATH}/port{connector_nr}/port{connector_nr}-partner/port{connector_nr}-partner.{num_alt_mode}"
+                    )
+                }
+                GetAlternateModesRecipient::SopPrime => {
+                    format!(
+                        "{SYSFS_TYPEC_PATH}/port{connector_nr}-cable/port{connector_nr}-plug0/port{connector_nr}-plug0.{num_alt_mode}"
+                    )
+                }
+                _ => {
+                    return Err(Error::NotSupported {
+                        #[cfg(feature = "backtrace")]
+                        backtrace: crate::backtrace::Backtrace::capture(),
+                    })
+                }
+            };
+
+            let mut alt_mode = UcsiAlternateMode::default();
+
+            let svid = match read_to_string(&format!("{path_str}/svid")).await {
+                Ok(content) => content,
+                Err(_) => break,
+            };
+            alt_mode.svid[0] = decode::parse_hex_u32(&svid)?;
+
+            let vdo = match read_to_string(&format!("{path_str}/vdo")).await {
+                Ok(content) => content,
+                Err(_) => break,
+            };
+            alt_mode.vdo[0] = decode::parse_hex_u32(&vdo)?;
+
+            alt_modes.push(alt_mode);
+        }
+
+        Ok(alt_modes)
+    }
+
+    async fn cable_properties(&mut self, connector_nr: usize) -> Result<UcsiCableProperty> {
+        let path_str = format!("{SYSFS_TYPEC_PATH}/port{connector_nr}-cable");
+
+        let content = read_to_string(&format!("{path_str}/plug_type")).await?;
+        let plug_end_type = decode::parse_cable_plug_type(&content)?;
+
+        let content = read_to_string(&format!("{path_str}/type")).await?;
+        let cable_type = decode::parse_cable_type(&content)?;
+
+        let content = read_to_string(&format!(
+            "{SYSFS_TYPEC_PATH}/port{connector_nr}-plug0/number_of_alternate_modes"
+        ))
+        .await?;
+        let mode_support = decode::parse_cable_mode_support(&content)?;
+
+        Ok(UcsiCableProperty {
+            plug_end_type,
+            cable_type,
+            mode_support,
+            ..Default::default()
+        })
+    }
+
+    async fn connector_status(&mut self, connector_nr: usize) -> Result<UcsiConnectorStatus> {
+        let mut connector_status = UcsiConnectorStatus::default();
+
+        let partner_path_str = format!("{SYSFS_TYPEC_PATH}/port{connector_nr}/port{connector_nr}-partner");
+        connector_status.connect_status = tokio::fs::try_exists(&partner_path_str).await.unwrap_or(false);
+
+        let psy_path_str = format!("{SYSFS_PSY_PATH}/ucsi-source-psy-USBC000:00{}", connector_nr + 1);
+
+        let content = read_to_string(&format!("{psy_path_str}/online")).await?;
+        let online = decode::parse_hex_u32(&content)?;
+
+        if online != 0 {
+            let content = read_to_string(&format!("{psy_path_str}/current_now")).await?;
+            let cur = decode::parse_u32(&content)? / 1000;
+
+            let content = read_to_string(&format!("{psy_path_str}/voltage_now")).await?;
+            let volt = decode::parse_u32(&content)? / 1000;
+
+            let op_mw = (cur * volt) / (250 * 1000);
+
+            let content = read_to_string(&format!("{psy_path_str}/current_max")).await?;
+            let cur = decode::parse_u32(&content)? / 1000;
+
+            let content = read_to_string(&format!("{psy_path_str}/voltage_max")).await?;
+            let volt = decode::parse_u32(&content)? / 1000;
+
+            let max_mw = (cur * volt) / (250 * 1000);
+
+            connector_status.negotiated_power_level = (op_mw << 10) | (max_mw) & 0x3ff;
+        }
+
+        Ok(connector_status)
+    }
+
+    async fn pd_message(
+        &mut self,
+        _connector_nr: usize,
+        _recipient: PdMessageRecipient,
+        _response_type: PdMessageResponseType,
+    ) -> Result<PdMessage> {
+        // The PD message decode in `crate::source` works over the
+        // synchronous `TypecSource` trait, which this backend does not
+        // implement, so every response type is reported as not supported.
+        Err(Error::NotSupported {
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn pdos(
+        &mut self,
+        _connector_nr: usize,
+        _partner_pdo: bool,
+        _pdo_offset: u32,
+        _nr_pdos: usize,
+        _src_or_sink_pdos: crate::ucsi::GetPdosSrcOrSink,
+        _pdo_type: crate::ucsi::GetPdoSourceCapabilitiesType,
+        _revision: BcdWrapper,
+    ) -> Result<Vec<PdPdo>> {
+        Err(Error::NotSupported {
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })
+    }
+}
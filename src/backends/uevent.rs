@@ -0,0 +1,344 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Kernel uevent notifications for the `typec`/`power_supply` subsystems.
+//!
+//! Detecting a plug/unplug or role change today means re-polling
+//! [`crate::OsBackend::capabilities`]/[`crate::OsBackend::connector_status`]
+//! on a timer, which re-walks the whole `/sys/class/typec` tree on every
+//! tick. [`UeventMonitor`] instead listens on the `NETLINK_KOBJECT_UEVENT`
+//! socket the kernel already uses to announce device changes, and turns
+//! matching uevents into a typed [`ConnectorStatusChangeEvent`], mirroring
+//! UCSI's own asynchronous connector-change notification model. Consumers
+//! get push updates and can lazily re-read only the attributes flagged as
+//! changed.
+
+use std::os::fd::AsRawFd;
+use std::os::fd::FromRawFd;
+use std::os::fd::OwnedFd;
+
+use nix::libc;
+
+use crate::ucsi::ConnectorStatusChange;
+use crate::Error;
+use crate::Result;
+
+const NETLINK_KOBJECT_UEVENT: libc::c_int = 15;
+/// The kernel's default uevent multicast group.
+const UEVENT_GROUP: u32 = 1;
+
+/// A uevent-derived connector status change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectorStatusChangeEvent {
+    pub connector_nr: usize,
+    pub changed_fields: ConnectorStatusChange,
+}
+
+/// Listens for `typec`/`power_supply` kernel uevents.
+pub struct UeventMonitor {
+    socket: OwnedFd,
+}
+
+impl UeventMonitor {
+    /// Opens a `NETLINK_KOBJECT_UEVENT` socket subscribed to the kernel's
+    /// default uevent multicast group.
+    pub fn new() -> Result<Self> {
+        // SAFETY: `libc::socket` is called with well-formed arguments; the
+        // returned fd is checked for failure before being wrapped.
+        let fd = unsafe { libc::socket(libc::AF_NETLINK, libc::SOCK_RAW, NETLINK_KOBJECT_UEVENT) };
+        if fd < 0 {
+            return Err(Error::NixError {
+                source: nix::Error::last(),
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            });
+        }
+        // SAFETY: `fd` was just returned by a successful `socket()` call and
+        // is not owned anywhere else.
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        addr.nl_pid = 0;
+        addr.nl_groups = UEVENT_GROUP;
+
+        // SAFETY: `addr` is a valid, fully-initialized `sockaddr_nl`.
+        let ret = unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                std::ptr::addr_of!(addr) as *const libc::sockaddr,
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if ret < 0 {
+            return Err(Error::NixError {
+                source: nix::Error::last(),
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            });
+        }
+
+        Ok(Self { socket })
+    }
+
+    /// Blocks until the next `typec`/`power_supply` uevent arrives, then
+    /// returns the status change it implies. Uevents for other subsystems,
+    /// or that don't map to a connector number, are skipped transparently.
+    pub fn next_change(&mut self) -> Result<ConnectorStatusChangeEvent> {
+        loop {
+            let datagram = self.recv_raw()?;
+            if let Some(event) = parse_uevent(&datagram) {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Blocks until the next uevent datagram arrives and returns its raw
+    /// bytes, unfiltered. Shared by [`Self::next_change`] and
+    /// [`ConnectorMonitor`], which classify the same datagrams differently.
+    fn recv_raw(&mut self) -> Result<Vec<u8>> {
+        let mut buf = [0u8; 2048];
+        // SAFETY: `buf` is a valid, appropriately-sized buffer.
+        let n = unsafe {
+            libc::recv(
+                self.socket.as_raw_fd(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+                0,
+            )
+        };
+        if n < 0 {
+            return Err(Error::NixError {
+                source: nix::Error::last(),
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            });
+        }
+
+        Ok(buf[..n as usize].to_vec())
+    }
+}
+
+/// A typed, higher-level connector-change notification classified from a
+/// kernel uevent, mirroring UCSI's own connector-change mechanism:
+/// `PartnerConnected`/`PartnerDisconnected` for `portN-partner` add/remove,
+/// `AltModeEntered` for a `portN-partner.M` alternate mode device appearing,
+/// and `PdContractChanged` for the connector's `power_supply` node changing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectorEvent {
+    PartnerConnected { connector_nr: usize },
+    PartnerDisconnected { connector_nr: usize },
+    PdContractChanged { connector_nr: usize },
+    AltModeEntered { connector_nr: usize, alt_mode_nr: usize },
+}
+
+/// Subscribes to kernel `typec`/`power_supply` uevents and yields them as
+/// typed [`ConnectorEvent`]s, so a daemon can react to plug events instead
+/// of busy-polling the sysfs tree with repeated
+/// [`crate::OsBackend::connector_status`] calls.
+pub struct ConnectorMonitor {
+    inner: UeventMonitor,
+}
+
+impl ConnectorMonitor {
+    /// Opens a `NETLINK_KOBJECT_UEVENT` socket subscribed to the kernel's
+    /// default uevent multicast group.
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            inner: UeventMonitor::new()?,
+        })
+    }
+
+    /// Blocks until the next classifiable connector event arrives. Uevents
+    /// that don't map to one of the tracked event kinds are skipped
+    /// transparently.
+    pub fn next_event(&mut self) -> Result<ConnectorEvent> {
+        loop {
+            let datagram = self.inner.recv_raw()?;
+            if let Some(event) = classify_connector_event(&datagram) {
+                return Ok(event);
+            }
+        }
+    }
+}
+
+/// Classifies one uevent datagram into a [`ConnectorEvent`], or `None` if
+/// it's for an unrelated subsystem or doesn't map to a tracked event kind.
+fn classify_connector_event(datagram: &[u8]) -> Option<ConnectorEvent> {
+    let text = String::from_utf8_lossy(datagram);
+
+    let mut subsystem = None;
+    let mut devpath = None;
+    let mut action = None;
+
+    for line in text.split('\0') {
+        if let Some(value) = line.strip_prefix("SUBSYSTEM=") {
+            subsystem = Some(value);
+        } else if let Some(value) = line.strip_prefix("DEVPATH=") {
+            devpath = Some(value);
+        } else if let Some(value) = line.strip_prefix("ACTION=") {
+            action = Some(value);
+        }
+    }
+
+    let devpath = devpath?;
+    let connector_nr = connector_nr_from_devpath(devpath)?;
+    let last_segment = devpath.rsplit('/').next()?;
+
+    match subsystem {
+        Some("power_supply") => {
+            if last_segment.contains("-psy") {
+                Some(ConnectorEvent::PdContractChanged { connector_nr })
+            } else {
+                None
+            }
+        }
+        Some("typec") => {
+            if let Some((_, alt_mode_nr)) = last_segment.split_once('.') {
+                if action == Some("add") {
+                    return Some(ConnectorEvent::AltModeEntered {
+                        connector_nr,
+                        alt_mode_nr: alt_mode_nr.parse().ok()?,
+                    });
+                }
+                return None;
+            }
+
+            if !last_segment.ends_with("-partner") {
+                return None;
+            }
+
+            match action? {
+                "add" => Some(ConnectorEvent::PartnerConnected { connector_nr }),
+                "remove" => Some(ConnectorEvent::PartnerDisconnected { connector_nr }),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Parses one uevent datagram's NUL-separated `KEY=value` lines, returning
+/// the connector status change it implies if it's for a `typec`/
+/// `power_supply` port.
+fn parse_uevent(datagram: &[u8]) -> Option<ConnectorStatusChangeEvent> {
+    let text = String::from_utf8_lossy(datagram);
+
+    let mut subsystem = None;
+    let mut devpath = None;
+    let mut action = None;
+
+    for line in text.split('\0') {
+        if let Some(value) = line.strip_prefix("SUBSYSTEM=") {
+            subsystem = Some(value);
+        } else if let Some(value) = line.strip_prefix("DEVPATH=") {
+            devpath = Some(value);
+        } else if let Some(value) = line.strip_prefix("ACTION=") {
+            action = Some(value);
+        }
+    }
+
+    if !matches!(subsystem, Some("typec") | Some("power_supply")) {
+        return None;
+    }
+
+    let connector_nr = connector_nr_from_devpath(devpath?)?;
+
+    let mut changed_fields = ConnectorStatusChange::default();
+    match action? {
+        "add" | "remove" | "change" => changed_fields.connector_partner_changed = true,
+        _ => return None,
+    }
+
+    Some(ConnectorStatusChangeEvent {
+        connector_nr,
+        changed_fields,
+    })
+}
+
+/// Extracts the connector number out of a `DEVPATH` such as
+/// `.../typec/port2` or `.../typec/port2-partner`.
+fn connector_nr_from_devpath(devpath: &str) -> Option<usize> {
+    let port_segment = devpath.split('/').find(|segment| segment.starts_with("port"))?;
+    let digits: String = port_segment
+        .trim_start_matches("port")
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_typec_change_uevent() {
+        let datagram = "ACTION=change\0DEVPATH=/devices/platform/typec/port0\0SUBSYSTEM=typec\0";
+        let event = parse_uevent(datagram.as_bytes()).unwrap();
+        assert_eq!(event.connector_nr, 0);
+        assert!(event.changed_fields.connector_partner_changed);
+    }
+
+    #[test]
+    fn parses_partner_devpath() {
+        let datagram = "ACTION=add\0DEVPATH=/devices/platform/typec/port1/port1-partner\0SUBSYSTEM=typec\0";
+        let event = parse_uevent(datagram.as_bytes()).unwrap();
+        assert_eq!(event.connector_nr, 1);
+    }
+
+    #[test]
+    fn ignores_unrelated_subsystems() {
+        let datagram = "ACTION=change\0DEVPATH=/devices/platform/some-other-dev\0SUBSYSTEM=usb\0";
+        assert!(parse_uevent(datagram.as_bytes()).is_none());
+    }
+
+    #[test]
+    fn classifies_partner_connected() {
+        let datagram = "ACTION=add\0DEVPATH=/devices/platform/typec/port1/port1-partner\0SUBSYSTEM=typec\0";
+        let event = classify_connector_event(datagram.as_bytes()).unwrap();
+        assert_eq!(
+            event,
+            ConnectorEvent::PartnerConnected { connector_nr: 1 }
+        );
+    }
+
+    #[test]
+    fn classifies_partner_disconnected() {
+        let datagram = "ACTION=remove\0DEVPATH=/devices/platform/typec/port1/port1-partner\0SUBSYSTEM=typec\0";
+        let event = classify_connector_event(datagram.as_bytes()).unwrap();
+        assert_eq!(
+            event,
+            ConnectorEvent::PartnerDisconnected { connector_nr: 1 }
+        );
+    }
+
+    #[test]
+    fn classifies_alt_mode_entered() {
+        let datagram =
+            "ACTION=add\0DEVPATH=/devices/platform/typec/port0/port0-partner/port0-partner.0\0SUBSYSTEM=typec\0";
+        let event = classify_connector_event(datagram.as_bytes()).unwrap();
+        assert_eq!(
+            event,
+            ConnectorEvent::AltModeEntered {
+                connector_nr: 0,
+                alt_mode_nr: 0
+            }
+        );
+    }
+
+    #[test]
+    fn classifies_pd_contract_changed() {
+        let datagram = "ACTION=change\0DEVPATH=/devices/platform/typec/port0/ucsi-source-psy-port0\0SUBSYSTEM=power_supply\0";
+        let event = classify_connector_event(datagram.as_bytes()).unwrap();
+        assert_eq!(
+            event,
+            ConnectorEvent::PdContractChanged { connector_nr: 0 }
+        );
+    }
+
+    #[test]
+    fn ignores_unclassifiable_typec_change() {
+        let datagram = "ACTION=change\0DEVPATH=/devices/platform/typec/port0\0SUBSYSTEM=typec\0";
+        assert!(classify_connector_event(datagram.as_bytes()).is_none());
+    }
+}
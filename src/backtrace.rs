@@ -0,0 +1,269 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! An internal abstraction over backtrace capture, so the `backtrace`
+//! feature doesn't require a nightly compiler.
+//!
+//! `std::backtrace::Backtrace` has been stable since Rust 1.65, independent
+//! of the still-nightly-only `error_generic_member_access` feature this
+//! crate used to gate on. `build.rs` probes the compiler version and emits
+//! `cfg(std_backtrace)` when it's new enough, in which case this module
+//! just re-exports the standard library's types. On an older toolchain,
+//! the `backtrace` feature falls back to a drop-in built on the
+//! `backtrace` crate instead. With neither available, `Backtrace` is an
+//! uninhabited type, so any field holding one compiles away.
+
+#[cfg(std_backtrace)]
+pub use std::backtrace::Backtrace;
+#[cfg(std_backtrace)]
+pub use std::backtrace::BacktraceStatus;
+
+#[cfg(all(not(std_backtrace), feature = "backtrace"))]
+pub use capture::Backtrace;
+#[cfg(all(not(std_backtrace), feature = "backtrace"))]
+pub use capture::BacktraceStatus;
+
+#[cfg(all(not(std_backtrace), not(feature = "backtrace")))]
+pub use disabled::Backtrace;
+#[cfg(all(not(std_backtrace), not(feature = "backtrace")))]
+pub use disabled::BacktraceStatus;
+
+#[cfg(all(not(std_backtrace), feature = "backtrace"))]
+mod capture {
+    use std::fmt;
+
+    /// Whether [`Backtrace::capture`] actually walked the stack.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum BacktraceStatus {
+        /// Capture ran, but didn't find any frames.
+        Unsupported,
+        /// Neither `RUST_LIB_BACKTRACE` nor `RUST_BACKTRACE` was set.
+        Disabled,
+        /// The stack was walked and at least one frame was recorded.
+        Captured,
+    }
+
+    /// A `backtrace`-crate-backed drop-in for `std::backtrace::Backtrace`,
+    /// for toolchains too old to have the real thing. Frames are captured
+    /// eagerly (they're just instruction pointers), but only symbolized
+    /// lazily, in [`fmt::Display::fmt`], since resolving debug info is the
+    /// expensive part.
+    pub struct Backtrace {
+        frames: Vec<backtrace::Frame>,
+        status: BacktraceStatus,
+    }
+
+    /// The three `RUST_BACKTRACE`/`RUST_LIB_BACKTRACE` settings Rust
+    /// tooling conventionally honors: unset or `"0"`/`"no"` disables
+    /// capture outright; `"full"` renders every frame verbatim, addresses
+    /// included; anything else (e.g. `"1"`) captures but renders a trimmed,
+    /// address-free view with library noise cut from both ends.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Verbosity {
+        Disabled,
+        Short,
+        Full,
+    }
+
+    fn verbosity() -> Verbosity {
+        let value = std::env::var("RUST_LIB_BACKTRACE").or_else(|_| std::env::var("RUST_BACKTRACE"));
+        match value.as_deref() {
+            Ok("0") | Ok("no") | Err(_) => Verbosity::Disabled,
+            Ok("full") => Verbosity::Full,
+            Ok(_) => Verbosity::Short,
+        }
+    }
+
+    /// Frame name prefixes that belong to this crate's own capture/`From`
+    /// conversion machinery, cut from the front of a trimmed backtrace —
+    /// they're always the innermost frames, since they ran right where
+    /// `Backtrace::capture()` was called.
+    const LEADING_NOISE_PREFIXES: &[&str] = &[
+        "libtypec_rs::backtrace::",
+        "backtrace::backtrace::",
+        "backtrace::capture::",
+        "core::convert::From::from",
+        "<libtypec_rs::Error as core::convert::From",
+    ];
+
+    /// Frame name prefixes belonging to the runtime's own startup/panic
+    /// machinery, cut from the back of a trimmed backtrace.
+    const TRAILING_NOISE_PREFIXES: &[&str] = &["std::rt::", "std::panicking::", "core::ops::function::"];
+
+    struct ResolvedFrame {
+        ip: usize,
+        name: Option<String>,
+        file: Option<String>,
+        line: Option<u32>,
+    }
+
+    impl ResolvedFrame {
+        fn is_noise(&self, prefixes: &[&str]) -> bool {
+            self.name
+                .as_deref()
+                .is_some_and(|name| prefixes.iter().any(|prefix| name.starts_with(prefix)))
+        }
+
+        fn write(&self, f: &mut fmt::Formatter<'_>, n: usize, full: bool) -> fmt::Result {
+            let name = self.name.as_deref().unwrap_or("<unknown>");
+
+            let location = match (&self.file, self.line) {
+                (Some(file), Some(line)) => {
+                    let file = if full { file.as_str() } else { shorten(file) };
+                    format!(" at {file}:{line}")
+                }
+                _ => String::new(),
+            };
+
+            if full {
+                writeln!(f, "#{n:<2} {:#x} - {name}{location}", self.ip)
+            } else {
+                writeln!(f, "#{n:<2} {name}{location}")
+            }
+        }
+    }
+
+    /// Drops everything before the crate-relative `src/...` portion of an
+    /// absolute file path, so frames don't all repeat the same build-host
+    /// checkout prefix.
+    fn shorten(file: &str) -> &str {
+        match file.rfind("src/") {
+            Some(idx) => &file[idx..],
+            None => file,
+        }
+    }
+
+    /// Finds the subslice bounds that drop leading and trailing noise
+    /// frames (see [`LEADING_NOISE_PREFIXES`]/[`TRAILING_NOISE_PREFIXES`]),
+    /// falling back to the full range if trimming would leave nothing.
+    fn trimmed_range(frames: &[ResolvedFrame]) -> (usize, usize) {
+        let start = frames
+            .iter()
+            .position(|frame| !frame.is_noise(LEADING_NOISE_PREFIXES))
+            .unwrap_or(0);
+
+        let end = frames
+            .iter()
+            .rposition(|frame| !frame.is_noise(TRAILING_NOISE_PREFIXES))
+            .map(|idx| idx + 1)
+            .unwrap_or(frames.len());
+
+        if start < end {
+            (start, end)
+        } else {
+            (0, frames.len())
+        }
+    }
+
+    impl Backtrace {
+        /// Walks the stack, honoring the same `RUST_LIB_BACKTRACE`/
+        /// `RUST_BACKTRACE` convention as the standard library: capture is
+        /// a no-op (returns [`BacktraceStatus::Disabled`]) unless one of
+        /// them requests it.
+        pub fn capture() -> Self {
+            if verbosity() == Verbosity::Disabled {
+                return Self {
+                    frames: Vec::new(),
+                    status: BacktraceStatus::Disabled,
+                };
+            }
+
+            let mut frames = Vec::new();
+            backtrace::trace(|frame| {
+                frames.push(frame.clone());
+                true
+            });
+
+            Self {
+                status: if frames.is_empty() {
+                    BacktraceStatus::Unsupported
+                } else {
+                    BacktraceStatus::Captured
+                },
+                frames,
+            }
+        }
+
+        /// Whether this backtrace actually captured any frames.
+        pub fn status(&self) -> BacktraceStatus {
+            self.status
+        }
+
+        fn resolve(&self) -> Vec<ResolvedFrame> {
+            self.frames
+                .iter()
+                .map(|frame| {
+                    let mut resolved = ResolvedFrame {
+                        ip: frame.ip() as usize,
+                        name: None,
+                        file: None,
+                        line: None,
+                    };
+                    backtrace::resolve_frame(frame, |symbol| {
+                        resolved.name = symbol.name().map(|name| name.to_string());
+                        resolved.file = symbol.filename().map(|path| path.display().to_string());
+                        resolved.line = symbol.lineno();
+                    });
+                    resolved
+                })
+                .collect()
+        }
+    }
+
+    impl fmt::Debug for Backtrace {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(self, f)
+        }
+    }
+
+    impl fmt::Display for Backtrace {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let full = verbosity() == Verbosity::Full;
+            let resolved = self.resolve();
+            let (start, end) = if full { (0, resolved.len()) } else { trimmed_range(&resolved) };
+
+            for (n, frame) in resolved[start..end].iter().enumerate() {
+                frame.write(f, n, full)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(all(not(std_backtrace), not(feature = "backtrace")))]
+mod disabled {
+    use std::fmt;
+
+    /// No way to capture a backtrace is available on this toolchain
+    /// without the `backtrace` feature. Uninhabited, so it costs nothing
+    /// and can never actually be constructed.
+    pub enum Backtrace {}
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BacktraceStatus {
+        /// The only possible status: capture is unavailable.
+        Unsupported,
+    }
+
+    impl Backtrace {
+        pub fn capture() -> Self {
+            unreachable!("Backtrace is uninhabited; nothing can construct one to call capture() on")
+        }
+
+        pub fn status(&self) -> BacktraceStatus {
+            match *self {}
+        }
+    }
+
+    impl fmt::Debug for Backtrace {
+        fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match *self {}
+        }
+    }
+
+    impl fmt::Display for Backtrace {
+        fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match *self {}
+        }
+    }
+}
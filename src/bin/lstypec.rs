@@ -7,13 +7,65 @@
 use argh::FromArgs;
 
 use libtypec_rs::typec::OsBackends;
+use libtypec_rs::typec::PdCapabilities;
 use libtypec_rs::typec::TypecRs;
 use libtypec_rs::ucsi::GetAlternateModesRecipient;
-use libtypec_rs::ucsi::GetPdoSourceCapabilitiesType;
-use libtypec_rs::ucsi::GetPdosSrcOrSink;
+use libtypec_rs::ucsi::PdMessage;
 use libtypec_rs::ucsi::PdMessageRecipient;
 use libtypec_rs::ucsi::PdMessageResponseType;
+use libtypec_rs::ucsi::UcsiAlternateMode;
+use libtypec_rs::ucsi::UcsiCableProperty;
+use libtypec_rs::ucsi::UcsiCapability;
+use libtypec_rs::ucsi::UcsiConnectorCapability;
 use libtypec_rs::Error;
+use libtypec_rs::Result;
+
+/// The two output formats the tool supports.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum OutputFormat {
+    /// Rust `{:#?}` debug dumps, one block per piece of data.
+    #[default]
+    Text,
+    /// A single JSON document describing every connector.
+    Json,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("unknown format {s:?} (expected \"text\" or \"json\")")),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// A single connector's full state, gathered from the individual
+/// [`TypecRs`] calls below so it can be printed either as text blocks or as
+/// one JSON object.
+struct ConnectorReport {
+    connector_nr: usize,
+    capability: UcsiConnectorCapability,
+    pd_capabilities: PdCapabilities,
+    partner_pd_capabilities: Option<PdCapabilities>,
+    cable_properties: Option<UcsiCableProperty>,
+    alternate_modes: Vec<UcsiAlternateMode>,
+    sop_prime_alternate_modes: Vec<UcsiAlternateMode>,
+    sop_alternate_modes: Vec<UcsiAlternateMode>,
+    sop_discover_identity: Option<PdMessage>,
+    sop_prime_discover_identity: Option<PdMessage>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// The full report: the PPM's own capabilities plus one entry per
+/// connector.
+struct Report {
+    capabilities: UcsiCapability,
+    connectors: Vec<ConnectorReport>,
+}
 
 #[derive(FromArgs)]
 /// List typec port and port partner details
@@ -24,6 +76,20 @@ struct Args {
     /// the backend to use
     #[argh(option)]
     backend: Option<OsBackends>,
+    /// output format: "text" (default) or "json"
+    #[argh(option, default = "OutputFormat::Text")]
+    format: OutputFormat,
+}
+
+/// Returns `Ok(None)` instead of propagating [`Error::NotSupported`], so
+/// callers can treat "not supported on this backend" the same as "not
+/// present on this connector" without a `match` at every call site.
+fn optional<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(Error::NotSupported { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
 }
 
 fn main() {
@@ -43,155 +109,140 @@ fn main() {
         .expect("No valid backend found");
 
     let capabilities = typec.capabilities().expect("Failed to get capabilities");
-    println!("USB-C Platform Policy Manager Capability");
-    println!("{:#?}", capabilities);
-    println!("");
 
+    let mut connectors = Vec::new();
     for connector_nr in 0..capabilities.num_connectors {
-        let conn_capability = typec
+        let capability = typec
             .connector_capabilties(connector_nr)
             .expect("Failed to get connector capabilities");
 
-        println!("Connector {connector_nr} Capability/Status");
-        println!("{:#?}", conn_capability);
-        println!("");
-
-        let conn_pdo = typec
-            .pdos(
-                connector_nr,
-                false,
-                0,
-                0,
-                GetPdosSrcOrSink::Source,
-                GetPdoSourceCapabilitiesType::CurrentSupportedSourceCapabilities,
-                capabilities.pd_version,
-            )
-            .expect("Failed to get Source PDOs");
-
-        println!("Connector {connector_nr} Source PDOs");
-        println!("{:#?}", conn_pdo);
-        println!("");
-
-        let conn_pdo = typec
-            .pdos(
-                connector_nr,
-                false,
-                0,
-                0,
-                GetPdosSrcOrSink::Sink,
-                GetPdoSourceCapabilitiesType::CurrentSupportedSourceCapabilities,
-                capabilities.pd_version,
-            )
-            .expect("Failed to get Sink PDOs");
-
-        println!("Connector {connector_nr} Sink PDOs");
-        println!("{:#?}", conn_pdo);
-        println!("");
-
-        match typec.cable_properties(connector_nr) {
-            Ok(cable_props) => {
-                println!("Connector {connector_nr} Cable Properties");
-                println!("{:#?}", cable_props);
-            }
-            Err(libtypec_rs::Error::NotSupported { .. }) => {
-                println!("No cable identified for {connector_nr}");
-            }
-            Err(e) => panic!("Failed to get cable properties for {connector_nr}: {:?}", e),
-        }
-        println!("");
+        let pd_capabilities = typec
+            .pd_capabilities(connector_nr, false)
+            .expect("Failed to get PD capabilities");
+
+        let partner_pd_capabilities =
+            optional(typec.pd_capabilities(connector_nr, true)).expect("Failed to get partner PD capabilities");
+
+        let cable_properties =
+            optional(typec.cable_properties(connector_nr)).expect("Failed to get cable properties");
 
         let alternate_modes = typec
             .alternate_modes(GetAlternateModesRecipient::Connector, connector_nr)
             .expect("Failed to get alternate modes");
 
-        println!("Connector {connector_nr} Alternate Modes");
-        println!("{:#?}", alternate_modes);
-        println!("");
-
-        let alternate_modes = typec
+        let sop_prime_alternate_modes = typec
             .alternate_modes(GetAlternateModesRecipient::SopPrime, connector_nr)
             .expect("Failed to get alternate modes");
 
-        println!("Connector {connector_nr} SOP' Alternate Modes");
-        println!("{:#?}", alternate_modes);
-        println!("");
+        let sop_alternate_modes = typec
+            .alternate_modes(GetAlternateModesRecipient::Sop, connector_nr)
+            .expect("Failed to get alternate modes");
 
-        match typec.pd_message(
+        let sop_discover_identity = optional(typec.pd_message(
             connector_nr,
             PdMessageRecipient::Sop,
             PdMessageResponseType::DiscoverIdentity,
-        ) {
-            Ok(pd_message) => {
-                println!("Connector {connector_nr} SOP DiscoverIdentity PD Message");
-                println!("{:#?}", pd_message);
-            }
-            Err(Error::NotSupported { .. }) => {}
-            Err(e) => panic!(
-                "Failed to get the DiscoverIdentity PD Message for SOP {:?}",
-                e
-            ),
-        };
-        println!("");
-
-        let alternate_modes = typec
-            .alternate_modes(GetAlternateModesRecipient::Sop, connector_nr)
-            .expect("Failed to get alternate modes");
+        ))
+        .expect("Failed to get the DiscoverIdentity PD Message for SOP");
 
-        println!("Connector {connector_nr} SOP' Alternate Modes");
-        println!("{:#?}", alternate_modes);
-        println!("");
-
-        match typec.pd_message(
+        let sop_prime_discover_identity = optional(typec.pd_message(
             connector_nr,
             PdMessageRecipient::SopPrime,
             PdMessageResponseType::DiscoverIdentity,
-        ) {
-            Ok(pd_message) => {
-                println!("Connector {connector_nr} SOP' DiscoverIdentity PD Message");
-                println!("{:#?}", pd_message);
-            }
-            Err(Error::NotSupported { .. }) => {}
-            Err(e) => panic!(
-                "Failed to get the DiscoverIdentity PD Message for SOP' {:?}",
-                e
-            ),
-        };
-        println!("");
-
-        match typec.pdos(
+        ))
+        .expect("Failed to get the DiscoverIdentity PD Message for SOP'");
+
+        connectors.push(ConnectorReport {
             connector_nr,
-            true,
-            0,
-            0,
-            GetPdosSrcOrSink::Source,
-            GetPdoSourceCapabilitiesType::CurrentSupportedSourceCapabilities,
-            capabilities.pd_version,
-        ) {
-            Ok(conn_pdo) => {
-                println!("Partner PDO data (Source)");
-                println!("{:#?}", conn_pdo);
+            capability,
+            pd_capabilities,
+            partner_pd_capabilities,
+            cable_properties,
+            alternate_modes,
+            sop_prime_alternate_modes,
+            sop_alternate_modes,
+            sop_discover_identity,
+            sop_prime_discover_identity,
+        });
+    }
+
+    let report = Report {
+        capabilities,
+        connectors,
+    };
+
+    match args.format {
+        OutputFormat::Text => print_text(&report),
+        OutputFormat::Json => print_json(&report),
+    }
+}
+
+fn print_text(report: &Report) {
+    println!("USB-C Platform Policy Manager Capability");
+    println!("{:#?}", report.capabilities);
+    println!();
+
+    for connector in &report.connectors {
+        let connector_nr = connector.connector_nr;
+
+        println!("Connector {connector_nr} Capability/Status");
+        println!("{:#?}", connector.capability);
+        println!();
+
+        println!("Connector {connector_nr} PD Capabilities");
+        println!("{:#?}", connector.pd_capabilities);
+        println!();
+
+        match &connector.cable_properties {
+            Some(cable_props) => {
+                println!("Connector {connector_nr} Cable Properties");
+                println!("{:#?}", cable_props);
             }
-            Err(Error::NotSupported { .. }) => {}
-            Err(e) => panic!("Failed to get Source PDOs {:?}", e),
+            None => println!("No cable identified for {connector_nr}"),
         }
-        println!("");
+        println!();
 
-        match typec.pdos(
-            connector_nr,
-            true,
-            0,
-            0,
-            GetPdosSrcOrSink::Sink,
-            GetPdoSourceCapabilitiesType::CurrentSupportedSourceCapabilities,
-            capabilities.pd_version,
-        ) {
-            Ok(conn_pdo) => {
-                println!("Partner PDO data (Sink)");
-                println!("{:#?}", conn_pdo);
-            }
-            Err(Error::NotSupported { .. }) => {}
-            Err(e) => panic!("Failed to get Sink PDOs {:?}", e),
+        println!("Connector {connector_nr} Alternate Modes");
+        println!("{:#?}", connector.alternate_modes);
+        println!();
+
+        println!("Connector {connector_nr} SOP' Alternate Modes");
+        println!("{:#?}", connector.sop_prime_alternate_modes);
+        println!();
+
+        if let Some(pd_message) = &connector.sop_discover_identity {
+            println!("Connector {connector_nr} SOP DiscoverIdentity PD Message");
+            println!("{:#?}", pd_message);
+        }
+        println!();
+
+        println!("Connector {connector_nr} SOP Alternate Modes");
+        println!("{:#?}", connector.sop_alternate_modes);
+        println!();
+
+        if let Some(pd_message) = &connector.sop_prime_discover_identity {
+            println!("Connector {connector_nr} SOP' DiscoverIdentity PD Message");
+            println!("{:#?}", pd_message);
+        }
+        println!();
+
+        if let Some(partner_pd_capabilities) = &connector.partner_pd_capabilities {
+            println!("Partner PD Capabilities");
+            println!("{:#?}", partner_pd_capabilities);
         }
-        println!("");
+        println!();
     }
 }
+
+#[cfg(feature = "serde")]
+fn print_json(report: &Report) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(report).expect("Failed to serialize report to JSON")
+    );
+}
+
+#[cfg(not(feature = "serde"))]
+fn print_json(_report: &Report) {
+    panic!("JSON output requires the \"serde\" feature");
+}
@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Named byte-to-field conversions for ingesting raw captures (sysfs dumps,
+//! analyzer CSVs, ...) into the crate's typed PD scalars.
+//!
+//! This mirrors the small, named-conversion abstraction Vector uses for its
+//! remap language: a conversion is parsed from a short string name and then
+//! applied to a byte slice to produce a typed value.
+
+use std::str::FromStr;
+
+use crate::Milliamp;
+use crate::Millivolt;
+use crate::Milliwatt;
+
+/// A named conversion from a raw byte slice to a typed scalar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conversion {
+    /// Interpret the bytes as a raw byte string; no conversion is applied.
+    Bytes,
+    /// Interpret the bytes as a little-endian signed integer.
+    Int,
+    /// Interpret the bytes as a little-endian IEEE-754 float.
+    Float,
+    /// Interpret the bytes as a boolean (zero is `false`, anything else is
+    /// `true`).
+    Bool,
+    /// Interpret the bytes as a little-endian integer scaled into
+    /// [`Millivolt`] by the given unit size, in millivolts per raw unit.
+    Millivolt { scale: u32 },
+    /// Interpret the bytes as a little-endian integer scaled into
+    /// [`Milliamp`] by the given unit size, in milliamps per raw unit.
+    Milliamp { scale: u32 },
+    /// Interpret the bytes as a little-endian integer scaled into
+    /// [`Milliwatt`] by the given unit size, in milliwatts per raw unit.
+    Milliwatt { scale: u32 },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConversionError {
+    #[error("unknown conversion: {0}")]
+    UnknownConversion(String),
+    #[error("not enough bytes for this conversion: expected at least {expected}, got {got}")]
+    NotEnoughBytes { expected: usize, got: usize },
+}
+
+impl FromStr for Conversion {
+    type Err = ConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Unit-scaled conversions are spelled "millivolt:50" (50 mV/unit).
+        if let Some((name, scale)) = s.split_once(':') {
+            let scale: u32 = scale
+                .parse()
+                .map_err(|_| ConversionError::UnknownConversion(s.to_string()))?;
+            return match name {
+                "millivolt" => Ok(Conversion::Millivolt { scale }),
+                "milliamp" => Ok(Conversion::Milliamp { scale }),
+                "milliwatt" => Ok(Conversion::Milliwatt { scale }),
+                _ => Err(ConversionError::UnknownConversion(s.to_string())),
+            };
+        }
+
+        match s {
+            "bytes" => Ok(Conversion::Bytes),
+            "int" => Ok(Conversion::Int),
+            "float" => Ok(Conversion::Float),
+            "bool" => Ok(Conversion::Bool),
+            other => Err(ConversionError::UnknownConversion(other.to_string())),
+        }
+    }
+}
+
+/// The typed result of applying a [`Conversion`] to a raw byte slice.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConvertedValue {
+    Bytes(Vec<u8>),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Millivolt(Millivolt),
+    Milliamp(Milliamp),
+    Milliwatt(Milliwatt),
+}
+
+impl Conversion {
+    /// Applies this conversion to `bytes`, producing a typed value.
+    pub fn convert(&self, bytes: &[u8]) -> Result<ConvertedValue, ConversionError> {
+        fn read_u32_le(bytes: &[u8]) -> Result<u32, ConversionError> {
+            let arr: [u8; 4] = bytes
+                .get(..4)
+                .ok_or(ConversionError::NotEnoughBytes {
+                    expected: 4,
+                    got: bytes.len(),
+                })?
+                .try_into()
+                .expect("slice of length 4 always converts to [u8; 4]");
+            Ok(u32::from_le_bytes(arr))
+        }
+
+        match self {
+            Conversion::Bytes => Ok(ConvertedValue::Bytes(bytes.to_vec())),
+            Conversion::Int => Ok(ConvertedValue::Int(read_u32_le(bytes)? as i32 as i64)),
+            Conversion::Float => {
+                let arr: [u8; 4] = bytes
+                    .get(..4)
+                    .ok_or(ConversionError::NotEnoughBytes {
+                        expected: 4,
+                        got: bytes.len(),
+                    })?
+                    .try_into()
+                    .expect("slice of length 4 always converts to [u8; 4]");
+                Ok(ConvertedValue::Float(f32::from_le_bytes(arr) as f64))
+            }
+            Conversion::Bool => Ok(ConvertedValue::Bool(bytes.first().copied().unwrap_or(0) != 0)),
+            Conversion::Millivolt { scale } => {
+                Ok(ConvertedValue::Millivolt((read_u32_le(bytes)? * scale).into()))
+            }
+            Conversion::Milliamp { scale } => {
+                Ok(ConvertedValue::Milliamp((read_u32_le(bytes)? * scale).into()))
+            }
+            Conversion::Milliwatt { scale } => {
+                Ok(ConvertedValue::Milliwatt((read_u32_le(bytes)? * scale).into()))
+            }
+        }
+    }
+}
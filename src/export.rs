@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Structured export of decoded PD traffic.
+//!
+//! [`encode_json`]/[`decode_json`] round-trip a full [`Message`], fields and
+//! all, for archival or debugging. [`encode_message_tag`]/
+//! [`decode_message_tag`] are a much narrower companion: a one-byte, stable,
+//! archivable tag identifying which [`Message`] variant a blob holds,
+//! without encoding any of its fields. They exist for callers that need a
+//! compact "what kind of message was this" marker and don't want to pull in
+//! `serde` for it; reach for the JSON pair instead if you need the message's
+//! contents back out.
+
+use crate::pd::Message;
+use crate::Error;
+use crate::Result;
+
+/// Serializes a decoded [`Message`] to a pretty-printed JSON document.
+#[cfg(feature = "serde")]
+pub fn encode_json(message: &Message) -> Result<String> {
+    serde_json::to_string_pretty(message).map_err(|source| Error::IoError {
+        source: std::io::Error::new(std::io::ErrorKind::InvalidData, source),
+        #[cfg(feature = "backtrace")]
+        backtrace: crate::backtrace::Backtrace::capture(),
+    })
+}
+
+/// Deserializes a [`Message`] previously produced by [`encode_json`].
+#[cfg(feature = "serde")]
+pub fn decode_json(json: &str) -> Result<Message> {
+    serde_json::from_str(json).map_err(|source| Error::IoError {
+        source: std::io::Error::new(std::io::ErrorKind::InvalidData, source),
+        #[cfg(feature = "backtrace")]
+        backtrace: crate::backtrace::Backtrace::capture(),
+    })
+}
+
+/// The stable wire tag for each [`Message`] variant. This identifies which
+/// variant a [`encode_message_tag`]-tagged blob holds, and must never be
+/// reordered: consumers archive this value, so it has to stay stable across
+/// crate versions.
+#[repr(u8)]
+enum MessageTag {
+    SinkCapabilitiesExtended = 1,
+    SourceCapabilitiesExtended = 2,
+    BatteryCapabilities = 3,
+    BatteryStatus = 4,
+    DiscoverIdentityResponse = 5,
+    Revision = 6,
+    Control = 7,
+    Request = 8,
+}
+
+/// Encodes a `u64` as a base-128 varint, matching protobuf's scalar
+/// encoding.
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.push(byte);
+            break;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+fn read_varint(buf: &[u8], offset: &mut usize) -> Result<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *buf.get(*offset).ok_or_else(|| Error::ParseError {
+            field: "varint".into(),
+            value: 0,
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })?;
+        *offset += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok(value)
+}
+
+/// Encodes which [`Message`] variant `message` is as a one-byte
+/// [`MessageTag`], varint-wrapped.
+///
+/// This only encodes the variant, not its fields — see the module-level
+/// docs. Use [`encode_json`] if you need the fields back out.
+pub fn encode_message_tag(message: &Message) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let tag = match message {
+        Message::Pd3p2SinkCapabilitiesExtended(_) => MessageTag::SinkCapabilitiesExtended,
+        Message::Pd3p2SourceCapabilitiesExtended(_) => MessageTag::SourceCapabilitiesExtended,
+        Message::Pd3p2BatteryCapabilities(_) => MessageTag::BatteryCapabilities,
+        Message::Pd3p2BatteryStatus(_) => MessageTag::BatteryStatus,
+        Message::Pd3p2DiscoverIdentityResponse(_) => MessageTag::DiscoverIdentityResponse,
+        Message::Pd3p2Revision(_) => MessageTag::Revision,
+        Message::Pd3p2Control(_) => MessageTag::Control,
+        Message::Pd3p2Request(_) => MessageTag::Request,
+    };
+    write_varint(&mut buf, tag as u64);
+    buf
+}
+
+/// The companion decoder for [`encode_message_tag`].
+pub fn decode_message_tag(bytes: &[u8]) -> Result<u8> {
+    let mut offset = 0;
+    Ok(read_varint(bytes, &mut offset)? as u8)
+}
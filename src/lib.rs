@@ -15,8 +15,6 @@
 // Rust types used throughout the library retain a (more verbose) full name in
 // spite of the module they are declared in.
 
-#![cfg_attr(feature = "backtrace", feature(error_generic_member_access))]
-
 use std::io::Cursor;
 
 use bitstream_io::LittleEndian;
@@ -34,7 +32,11 @@ use ucsi::UcsiConnectorCapability;
 use ucsi::UcsiConnectorStatus;
 
 pub mod backends;
+mod backtrace;
+pub mod conversion;
+pub mod export;
 pub mod pd;
+pub mod source;
 pub mod typec;
 pub mod ucsi;
 pub mod vdo;
@@ -57,6 +59,13 @@ pub trait OsBackend {
 
     fn cable_properties(&mut self, connector_nr: usize) -> Result<UcsiCableProperty>;
 
+    /// Decodes the cable's Discover Identity ACK (SOP') on `connector_nr`
+    /// into typed VDOs: ID Header, Cert Stat and Product (see
+    /// [`OsBackend::pd_message`]'s [`PdMessageResponseType::DiscoverIdentity`]
+    /// for the partner equivalent), plus the Cable VDO describing the
+    /// cable assembly's own electrical capabilities.
+    fn cable_identity(&mut self, connector_nr: usize) -> Result<vdo::CableIdentity>;
+
     fn connector_status(&mut self, connector_nr: usize) -> Result<UcsiConnectorStatus>;
 
     fn pd_message(
@@ -77,6 +86,82 @@ pub trait OsBackend {
         pdo_type: GetPdoSourceCapabilitiesType,
         revision: BcdWrapper,
     ) -> Result<Vec<PdPdo>>;
+
+    /// Requests a power role swap on `connector_nr`. Returns
+    /// [`Error::NotSupported`] if the connector's advertised capabilities
+    /// (see [`OsBackend::connector_capabilties`]) don't allow swapping to
+    /// `role`.
+    fn set_power_role(&mut self, connector_nr: usize, role: crate::ucsi::PowerRole) -> Result<()>;
+
+    /// Requests a data role swap on `connector_nr`. Returns
+    /// [`Error::NotSupported`] if the connector's advertised capabilities
+    /// don't allow swapping to `role`.
+    fn set_data_role(&mut self, connector_nr: usize, role: crate::ucsi::DataRole) -> Result<()>;
+
+    /// Requests that `connector_nr` be put into `mode` (DRP, Rp only, Rd
+    /// only, ...). Returns [`Error::NotSupported`] if the connector doesn't
+    /// advertise that operation mode.
+    fn set_usb_operation_mode(
+        &mut self,
+        connector_nr: usize,
+        mode: crate::ucsi::ConnectorCapabilityOperationMode,
+    ) -> Result<()>;
+
+    /// Enters (`enter == true`) or exits the alternate mode at `alt_mode_nr`
+    /// on `connector_nr`.
+    fn set_alternate_mode(&mut self, connector_nr: usize, alt_mode_nr: usize, enter: bool) -> Result<()>;
+
+    /// Issues a PD reset on `connector_nr`: a soft reset (`hard_reset ==
+    /// false`) renegotiates the PD contract without dropping VBUS/VCONN,
+    /// while a hard reset tears the connection down and re-establishes it
+    /// from scratch. Backends that have no way to trigger this (e.g. the
+    /// `typec` sysfs class exposes no reset attribute) return
+    /// [`Error::NotSupported`].
+    fn connector_reset(&mut self, connector_nr: usize, hard_reset: bool) -> Result<()>;
+}
+
+/// An async counterpart to [`OsBackend`], for callers that want
+/// event-loop-friendly access instead of blocking on every call (e.g. inside
+/// a tokio-based daemon). It mirrors the read methods of [`OsBackend`]
+/// (`capabilities` through `pdos`) with the same arguments; it has no
+/// counterpart of `cable_identity`, of the role and USB operation mode
+/// setters, of `set_alternate_mode` or of `connector_reset`. Only the UCSI
+/// decode logic is shared between implementations, never duplicated.
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncOsBackend {
+    async fn capabilities(&mut self) -> Result<UcsiCapability>;
+
+    async fn connector_capabilties(&mut self, connector_nr: usize) -> Result<UcsiConnectorCapability>;
+
+    async fn alternate_modes(
+        &mut self,
+        recipient: GetAlternateModesRecipient,
+        connector_nr: usize,
+    ) -> Result<Vec<UcsiAlternateMode>>;
+
+    async fn cable_properties(&mut self, connector_nr: usize) -> Result<UcsiCableProperty>;
+
+    async fn connector_status(&mut self, connector_nr: usize) -> Result<UcsiConnectorStatus>;
+
+    async fn pd_message(
+        &mut self,
+        connector_nr: usize,
+        recipient: PdMessageRecipient,
+        response_type: PdMessageResponseType,
+    ) -> Result<PdMessage>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn pdos(
+        &mut self,
+        connector_nr: usize,
+        partner_pdo: bool,
+        pdo_offset: u32,
+        nr_pdos: usize,
+        src_or_sink_pdos: GetPdosSrcOrSink,
+        pdo_type: GetPdoSourceCapabilitiesType,
+        revision: BcdWrapper,
+    ) -> Result<Vec<PdPdo>>;
 }
 
 /// A trait for serializing an object to a byte stream.
@@ -97,6 +182,7 @@ pub trait FromBytes {
         Self: Sized;
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 #[derive(Clone, Copy, Default, PartialEq)]
 /// A wrapper that can pretty-print the underlying BCD value.
@@ -108,6 +194,38 @@ impl std::fmt::Debug for BcdWrapper {
     }
 }
 
+impl BcdWrapper {
+    /// The major component of the BCD version, e.g. `1` in `0x0120` (1.2).
+    pub fn major(&self) -> u32 {
+        (self.0 >> 8) & 0xff
+    }
+
+    /// The minor component of the BCD version, e.g. `2` in `0x0120` (1.2).
+    pub fn minor(&self) -> u32 {
+        (self.0 >> 4) & 0xf
+    }
+
+    /// The subminor component of the BCD version, e.g. `0` in `0x0120`
+    /// (1.2.0).
+    pub fn subminor(&self) -> u32 {
+        self.0 & 0xf
+    }
+}
+
+/// An alias for [`BcdWrapper`] used where the value specifically identifies
+/// a UCSI specification version, e.g. `0x0120` for UCSI 1.2 or `0x0300` for
+/// UCSI 3.0.
+pub type UcsiVersion = BcdWrapper;
+
+impl UcsiVersion {
+    /// Converts a USB PD Specification Revision code (the 2-bit field used
+    /// throughout PD messages: `0b00`=1.0, `0b01`=2.0, `0b10`=3.0) to its BCD
+    /// representation.
+    pub fn from_pd_spec_revision(revision: u32) -> Self {
+        BcdWrapper((revision + 1) << 8)
+    }
+}
+
 #[derive(thiserror::Error)]
 /// An error type for the library.
 pub enum Error {
@@ -116,64 +234,77 @@ pub enum Error {
         #[from]
         source: nix::Error,
         #[cfg(feature = "backtrace")]
-        backtrace: std::backtrace::Backtrace,
+        backtrace: crate::backtrace::Backtrace,
     },
     #[error("{source}")]
     IoError {
         #[from]
         source: std::io::Error,
         #[cfg(feature = "backtrace")]
-        backtrace: std::backtrace::Backtrace,
+        backtrace: crate::backtrace::Backtrace,
     },
     #[error("This operation is not supported")]
     NotSupported {
         #[cfg(feature = "backtrace")]
-        backtrace: std::backtrace::Backtrace,
+        backtrace: crate::backtrace::Backtrace,
     },
     #[error("Could not parse field {field} with value {value}")]
     ParseError {
         field: String,
         value: u32,
         #[cfg(feature = "backtrace")]
-        backtrace: std::backtrace::Backtrace,
+        backtrace: crate::backtrace::Backtrace,
     },
     #[error("Could not parse field {field} with value {value}")]
     ParseStringError {
         field: String,
         value: String,
         #[cfg(feature = "backtrace")]
-        backtrace: std::backtrace::Backtrace,
+        backtrace: crate::backtrace::Backtrace,
     },
     #[error("{source}")]
     Utf8Error {
         #[from]
         source: std::str::Utf8Error,
         #[cfg(feature = "backtrace")]
-        backtrace: std::backtrace::Backtrace,
+        backtrace: crate::backtrace::Backtrace,
     },
     #[error("Timed out waiting for a response")]
     TimeoutError {
         #[cfg(feature = "backtrace")]
-        backtrace: std::backtrace::Backtrace,
+        backtrace: crate::backtrace::Backtrace,
     },
     #[error("This USB revision is not supported: {revision:?}")]
     UnsupportedUsbRevision {
         revision: BcdWrapper,
         #[cfg(feature = "backtrace")]
-        backtrace: std::backtrace::Backtrace,
+        backtrace: crate::backtrace::Backtrace,
     },
     #[error("{source}")]
     NulError {
         #[from]
         source: std::ffi::NulError,
         #[cfg(feature = "backtrace")]
-        backtrace: std::backtrace::Backtrace,
+        backtrace: crate::backtrace::Backtrace,
     },
     #[error("{source}")]
     DirError {
         source: Box<dyn std::error::Error + 'static>,
         #[cfg(feature = "backtrace")]
-        backtrace: std::backtrace::Backtrace,
+        backtrace: crate::backtrace::Backtrace,
+    },
+    #[error("{msg}: {source}")]
+    /// A lower-level error annotated with what the caller was attempting,
+    /// added by [`Context::context`]/[`Context::with_context`]. Nesting
+    /// these (e.g. a backend wrapping a `nix::Error` with "ioctl failed",
+    /// then the caller wrapping that with "getting connector 3 status")
+    /// walks the whole chain in [`Display`](std::fmt::Display), since
+    /// `source`'s own message is included recursively.
+    Context {
+        msg: String,
+        source: Box<Error>,
+        #[cfg(feature = "backtrace")]
+        backtrace: crate::backtrace::Backtrace,
     },
 }
 
@@ -191,7 +322,11 @@ impl std::fmt::Debug for Error {
                     .finish()?;
 
                 #[cfg(feature = "backtrace")]
-                write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                if matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured) {
+                    write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                } else {
+                    Ok(())
+                }
             }
             Self::IoError {
                 source,
@@ -201,7 +336,11 @@ impl std::fmt::Debug for Error {
                 f.debug_struct("IoError").field("source", source).finish()?;
 
                 #[cfg(feature = "backtrace")]
-                write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                if matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured) {
+                    write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                } else {
+                    Ok(())
+                }
             }
             Self::Utf8Error {
                 source,
@@ -213,7 +352,11 @@ impl std::fmt::Debug for Error {
                     .finish()?;
 
                 #[cfg(feature = "backtrace")]
-                write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                if matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured) {
+                    write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                } else {
+                    Ok(())
+                }
             }
             Self::NulError {
                 source,
@@ -225,7 +368,11 @@ impl std::fmt::Debug for Error {
                     .finish()?;
 
                 #[cfg(feature = "backtrace")]
-                write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                if matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured) {
+                    write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                } else {
+                    Ok(())
+                }
             }
             Self::DirError {
                 source,
@@ -237,7 +384,11 @@ impl std::fmt::Debug for Error {
                     .finish()?;
 
                 #[cfg(feature = "backtrace")]
-                write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                if matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured) {
+                    write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                } else {
+                    Ok(())
+                }
             }
             Self::NotSupported {
                 #[cfg(feature = "backtrace")]
@@ -246,7 +397,11 @@ impl std::fmt::Debug for Error {
                 f.debug_struct("NotSupported").finish()?;
 
                 #[cfg(feature = "backtrace")]
-                write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                if matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured) {
+                    write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                } else {
+                    Ok(())
+                }
             }
             Self::TimeoutError {
                 #[cfg(feature = "backtrace")]
@@ -255,7 +410,11 @@ impl std::fmt::Debug for Error {
                 f.debug_struct("TimeoutError").finish()?;
 
                 #[cfg(feature = "backtrace")]
-                write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                if matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured) {
+                    write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                } else {
+                    Ok(())
+                }
             }
             Self::ParseError {
                 field,
@@ -269,7 +428,11 @@ impl std::fmt::Debug for Error {
                     .finish()?;
 
                 #[cfg(feature = "backtrace")]
-                write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                if matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured) {
+                    write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                } else {
+                    Ok(())
+                }
             }
             Self::ParseStringError {
                 field,
@@ -283,7 +446,11 @@ impl std::fmt::Debug for Error {
                     .finish()?;
 
                 #[cfg(feature = "backtrace")]
-                write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                if matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured) {
+                    write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                } else {
+                    Ok(())
+                }
             }
             Self::UnsupportedUsbRevision {
                 revision,
@@ -295,7 +462,29 @@ impl std::fmt::Debug for Error {
                     .finish()?;
 
                 #[cfg(feature = "backtrace")]
-                write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                if matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured) {
+                    write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                } else {
+                    Ok(())
+                }
+            }
+            Self::Context {
+                msg,
+                source,
+                #[cfg(feature = "backtrace")]
+                backtrace,
+            } => {
+                f.debug_struct("Context")
+                    .field("msg", msg)
+                    .field("source", source)
+                    .finish()?;
+
+                #[cfg(feature = "backtrace")]
+                if matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured) {
+                    write!(f, "\n\nerror stack backtrace:\n{}", backtrace)
+                } else {
+                    Ok(())
+                }
             }
         }
         #[cfg(not(feature = "backtrace"))]
@@ -303,29 +492,182 @@ impl std::fmt::Debug for Error {
     }
 }
 
+impl Error {
+    /// Whether re-issuing the command that produced this error is likely
+    /// to help: transient I/O and timing problems are worth retrying,
+    /// parse/format/support errors aren't, since they'll just happen again.
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Error::NixError { .. } | Error::IoError { .. } | Error::TimeoutError { .. } => true,
+            // A context wrapper is only worth retrying if the failure it
+            // annotates is.
+            Error::Context { source, .. } => source.is_recoverable(),
+            _ => false,
+        }
+    }
+
+    /// The backtrace captured when this error occurred, regardless of which
+    /// variant it is. `None` if the `backtrace` feature isn't enabled, or
+    /// if capture was a no-op (see [`crate::backtrace::BacktraceStatus`]) —
+    /// which is the default unless `RUST_BACKTRACE`/`RUST_LIB_BACKTRACE` is
+    /// set, so capturing stays free on hot paths like PDO/VDO parsing. For
+    /// [`Error::Context`], delegates to the innermost captured trace in the
+    /// chain rather than the point where the context annotation was added.
+    pub fn backtrace(&self) -> Option<&crate::backtrace::Backtrace> {
+        #[cfg(feature = "backtrace")]
+        {
+            if let Error::Context { source, .. } = self {
+                return source.backtrace();
+            }
+
+            let backtrace = match self {
+                Error::NixError { backtrace, .. }
+                | Error::IoError { backtrace, .. }
+                | Error::NotSupported { backtrace }
+                | Error::ParseError { backtrace, .. }
+                | Error::ParseStringError { backtrace, .. }
+                | Error::Utf8Error { backtrace, .. }
+                | Error::TimeoutError { backtrace }
+                | Error::UnsupportedUsbRevision { backtrace, .. }
+                | Error::NulError { backtrace, .. }
+                | Error::DirError { backtrace, .. } => backtrace,
+                Error::Context { .. } => unreachable!("handled above"),
+            };
+
+            matches!(backtrace.status(), crate::backtrace::BacktraceStatus::Captured).then_some(backtrace)
+        }
+        #[cfg(not(feature = "backtrace"))]
+        {
+            None
+        }
+    }
+}
+
+/// anyhow-style context annotation for backend errors: wraps a lower-level
+/// failure (or an absent [`Option`]) with a human-readable description of
+/// what was being attempted, so a failure reads as "getting connector 3
+/// status: ioctl failed: ..." instead of an opaque `ENXIO`. Nesting
+/// annotations (e.g. a backend call site wrapping the command it issued,
+/// then its caller wrapping the connector it was acting on) builds up the
+/// whole chain, since [`Error::Context`]'s `Display` includes its source's.
+pub trait Context<T> {
+    /// Annotates the error (or `None`) with `c`, evaluated unconditionally
+    /// — prefer [`Context::with_context`] if `c` isn't free to construct.
+    fn context<C: std::fmt::Display + Send + Sync + 'static>(self, c: C) -> Result<T>;
+
+    /// Like [`Context::context`], but `f` is only called on the failure
+    /// path.
+    fn with_context<C: std::fmt::Display + Send + Sync + 'static, F: FnOnce() -> C>(self, f: F) -> Result<T>;
+}
+
+impl<T, E> Context<T> for std::result::Result<T, E>
+where
+    E: Into<Error>,
+{
+    fn context<C: std::fmt::Display + Send + Sync + 'static>(self, c: C) -> Result<T> {
+        self.with_context(|| c)
+    }
+
+    fn with_context<C: std::fmt::Display + Send + Sync + 'static, F: FnOnce() -> C>(self, f: F) -> Result<T> {
+        self.map_err(|e| Error::Context {
+            msg: f().to_string(),
+            source: Box::new(e.into()),
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })
+    }
+}
+
+impl<T> Context<T> for Option<T> {
+    fn context<C: std::fmt::Display + Send + Sync + 'static>(self, c: C) -> Result<T> {
+        self.with_context(|| c)
+    }
+
+    fn with_context<C: std::fmt::Display + Send + Sync + 'static, F: FnOnce() -> C>(self, f: F) -> Result<T> {
+        self.ok_or_else(|| Error::Context {
+            msg: f().to_string(),
+            source: Box::new(Error::NotSupported {
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            }),
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })
+    }
+}
+
 #[repr(transparent)]
 pub struct CError(pub std::ffi::c_int);
 
+std::thread_local! {
+    /// The calling thread's most recent [`Error`], stashed as a full
+    /// diagnostic message by [`From<Error> for CError`] so C callers can
+    /// retrieve the detail a bare errno throws away.
+    static LAST_ERROR: std::cell::RefCell<Option<std::ffi::CString>> = const { std::cell::RefCell::new(None) };
+}
+
 impl From<Error> for CError {
-    /// Converts an Error to a C error number
+    /// Converts an Error to a C error number, stashing its full diagnostic
+    /// message (the `Display` chain, then the `Debug` dump, including the
+    /// backtrace when one was captured) on this thread for
+    /// [`libtypec_rs_last_error_message`] to retrieve afterwards.
     fn from(err: Error) -> Self {
-        match err {
-            Error::NixError { source, .. } => CError(source as i32),
-            Error::IoError { source, .. } => {
-                CError(source.raw_os_error().unwrap_or(nix::libc::EIO))
-            }
-            Error::NotSupported { .. } => CError(nix::libc::EOPNOTSUPP),
-            Error::ParseError { .. }
-            | Error::Utf8Error { .. }
-            | Error::NulError { .. }
-            | Error::DirError { .. }
-            | Error::ParseStringError { .. } => CError(nix::libc::EIO),
-            Error::TimeoutError { .. } => CError(nix::libc::ETIMEDOUT),
-            Error::UnsupportedUsbRevision { .. } => CError(nix::libc::ENOTSUP),
-        }
+        let message = format!("{err}\n\n{err:?}");
+        LAST_ERROR.with(|last_error| {
+            *last_error.borrow_mut() = std::ffi::CString::new(message).ok();
+        });
+
+        CError(errno_for(&err))
     }
 }
 
+/// The errno this error maps to, recursing through [`Error::Context`] to
+/// the root cause rather than inventing one for the annotation wrapper
+/// itself. Split out of [`From<Error> for CError`] so that recursion
+/// doesn't re-stash [`LAST_ERROR`] with the inner cause's message, which
+/// would clobber the outer context annotation the caller actually wants.
+fn errno_for(err: &Error) -> std::ffi::c_int {
+    match err {
+        Error::NixError { source, .. } => *source as i32,
+        Error::IoError { source, .. } => source.raw_os_error().unwrap_or(nix::libc::EIO),
+        Error::NotSupported { .. } => nix::libc::EOPNOTSUPP,
+        Error::ParseError { .. }
+        | Error::Utf8Error { .. }
+        | Error::NulError { .. }
+        | Error::DirError { .. }
+        | Error::ParseStringError { .. } => nix::libc::EIO,
+        Error::TimeoutError { .. } => nix::libc::ETIMEDOUT,
+        Error::UnsupportedUsbRevision { .. } => nix::libc::ENOTSUP,
+        Error::Context { source, .. } => errno_for(source),
+    }
+}
+
+/// Returns the calling thread's most recent error message (as stashed by
+/// converting an [`Error`] to a [`CError`]), or a null pointer if there
+/// hasn't been one since the thread started or since the last
+/// [`libtypec_rs_clear_last_error`] call. The returned pointer is only
+/// valid until the next `Error`-to-`CError` conversion or
+/// `libtypec_rs_clear_last_error` call on this thread; callers must not
+/// free it.
+#[no_mangle]
+pub extern "C" fn libtypec_rs_last_error_message() -> *const std::ffi::c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Clears the calling thread's stashed error message, invalidating any
+/// pointer previously returned by [`libtypec_rs_last_error_message`].
+#[no_mangle]
+pub extern "C" fn libtypec_rs_clear_last_error() {
+    LAST_ERROR.with(|last_error| {
+        last_error.borrow_mut().take();
+    });
+}
+
 #[cfg(target_os = "linux")]
 fn is_chrome_os() -> Result<bool> {
     let uname = nix::sys::utsname::uname()?;
@@ -337,6 +679,7 @@ fn is_chrome_os() -> bool {
     false
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 #[derive(Clone, Copy, Default, PartialEq)]
 /// A wrapper that can pretty-print the underlying millivolt value.
@@ -354,6 +697,7 @@ impl From<u32> for Millivolt {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 #[derive(Clone, Copy, Default, PartialEq)]
 /// A wrapper that can pretty-print the underlying milliamp value.
@@ -371,6 +715,7 @@ impl From<u32> for Milliamp {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 #[derive(Clone, Copy, Default, PartialEq)]
 /// A wrapper that can pretty-print the underlying milliwatt value.
@@ -387,3 +732,54 @@ impl From<u32> for Milliwatt {
         Milliwatt(val)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn context_annotates_a_result_error() {
+        let result: std::result::Result<(), std::io::Error> =
+            Err(std::io::Error::new(std::io::ErrorKind::NotFound, "ioctl failed"));
+        let err = result.context("getting connector 3 status").unwrap_err();
+        assert_eq!(err.to_string(), "getting connector 3 status: ioctl failed");
+    }
+
+    #[test]
+    fn context_annotates_a_none_option_as_not_supported() {
+        let err = None::<()>.context("getting connector 3 status").unwrap_err();
+        assert!(matches!(&err, Error::Context { source, .. } if matches!(**source, Error::NotSupported { .. })));
+        assert_eq!(err.to_string(), "getting connector 3 status: This operation is not supported");
+    }
+
+    #[test]
+    fn with_context_is_lazy() {
+        let mut called = false;
+        let result: std::result::Result<(), std::io::Error> = Ok(());
+        result
+            .with_context(|| {
+                called = true;
+                "never evaluated on the success path"
+            })
+            .unwrap();
+        assert!(!called);
+    }
+
+    #[test]
+    fn context_nests_through_a_chain_of_annotations() {
+        let result: std::result::Result<(), std::io::Error> =
+            Err(std::io::Error::new(std::io::ErrorKind::NotFound, "ioctl failed"));
+        let err = result.context("reading online").context("getting connector 3 status").unwrap_err();
+        assert_eq!(err.to_string(), "getting connector 3 status: reading online: ioctl failed");
+    }
+
+    #[test]
+    fn context_preserves_the_source_errors_recoverability() {
+        let recoverable: std::result::Result<(), std::io::Error> =
+            Err(std::io::Error::new(std::io::ErrorKind::NotFound, "ioctl failed"));
+        assert!(recoverable.context("getting connector 3 status").unwrap_err().is_recoverable());
+
+        let not_recoverable = None::<()>.context("getting connector 3 status").unwrap_err();
+        assert!(!not_recoverable.is_recoverable());
+    }
+}
@@ -7,7 +7,9 @@
 //! See "Universal Serial Bus Power Delivery Specification"
 
 use bitstream_io::BitRead;
+use bitstream_io::BitWrite;
 use enumn::N;
+use proc_macros::BitCodec;
 use proc_macros::CApiWrapper;
 use proc_macros::Printf;
 use proc_macros::Snprintf;
@@ -16,9 +18,11 @@ use crate::pd::pd3p2::BatterySupplyPdo;
 use crate::pd::pd3p2::FixedSupplyPdo;
 use crate::BcdWrapper;
 use crate::BitReader;
+use crate::BitWriter;
 use crate::Error;
 use crate::FromBytes;
 use crate::Result;
+use crate::ToBytes;
 
 use crate::pd::pd3p2::BatteryCapData;
 use crate::pd::pd3p2::BatteryStatusData;
@@ -41,6 +45,7 @@ use crate::pd::pd3p2::VariableSupplyPdo;
 
 pub mod pd3p2;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Default, N, CApiWrapper)]
 #[c_api(prefix = "Pd", repr_c = true)]
 pub enum CommandType {
@@ -55,6 +60,7 @@ pub enum CommandType {
     Busy,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Default, N, Copy, CApiWrapper)]
 #[c_api(prefix = "Pd", repr_c = true)]
 pub enum Command {
@@ -73,17 +79,25 @@ pub enum Command {
     SVIDSpecific,
 }
 
-#[derive(Debug, Clone, PartialEq, Default, CApiWrapper)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default, CApiWrapper, BitCodec)]
 #[c_api(prefix = "Pd", repr_c = true)]
 /// The VDM header. See table 6.30 in the USB PD Specification for more
 /// information.
 pub struct VdmHeader {
+    /// The Standard or Vendor ID of the VDM's originator.
+    #[bits(16)]
+    pub vendor_id: u16,
     // Whether this is a structured VDM.
+    #[bits(1)]
     pub structured: bool,
-    // The major version number of this VDM.
-    pub major: u8,
-    // Them minor major version number of this VDM.
-    pub minor: u8,
+    /// The VDM version.
+    #[bits(2)]
+    pub version: u8,
+    /// bits12..11: reserved.
+    #[bits(2, reserved)]
+    #[c_api(no_prefix)]
+    pub reserved0: (),
     /// For Enter Mode, Exit Mode and Attention commands:
     ///
     /// Index into the list of VDOs to identify the desired Mode
@@ -91,24 +105,126 @@ pub struct VdmHeader {
     /// For Exit Mode only: 0b111 to exit all Active Modes
     ///
     /// Zero otherwise.
+    #[bits(3)]
     pub object_position: u8,
     /// The command type.
+    #[bits(2)]
     pub command_type: CommandType,
+    /// bit5: reserved.
+    #[bits(1, reserved)]
+    #[c_api(no_prefix)]
+    pub reserved1: (),
     /// The command.
+    #[bits(5)]
     pub command: Command,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default, CApiWrapper)]
+#[c_api(prefix = "Pd", repr_c = true)]
+/// SPR Adjustable Voltage Supply APDO. See USB PD 3.1 - Table 6.9 "SPR
+/// Adjustable Voltage Supply Augmented PDO".
+pub struct SprAdjustableVoltageSupplyPdo {
+    /// Peak Current, as a 2-bit code (same encoding as the Fixed Supply
+    /// PDO's Peak Current field).
+    pub peak_current: u8,
+    /// 100 mV units.
+    pub max_voltage: crate::Millivolt,
+    /// 100 mV units.
+    pub min_voltage: crate::Millivolt,
+}
+
+impl FromBytes for SprAdjustableVoltageSupplyPdo {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let _reserved = reader.read::<u32>(9)?;
+        let peak_current = reader.read::<u8>(2)?;
+        let max_voltage = (reader.read::<u32>(8)? * 100).into();
+        let _reserved = reader.read_bit()?;
+        let min_voltage = (reader.read::<u32>(8)? * 100).into();
+
+        Ok(Self {
+            peak_current,
+            max_voltage,
+            min_voltage,
+        })
+    }
+}
+
+impl ToBytes for SprAdjustableVoltageSupplyPdo {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write(9, 0u32)?;
+        bit_writer.write(2, self.peak_current)?;
+        bit_writer.write(8, self.max_voltage.0 / 100)?;
+        bit_writer.write_bit(false)?;
+        bit_writer.write(8, self.min_voltage.0 / 100)?;
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default, CApiWrapper)]
+#[c_api(prefix = "Pd", repr_c = true)]
+/// EPR Adjustable Voltage Supply APDO. See USB PD 3.2 - Table 6.10 "EPR
+/// Adjustable Voltage Supply Augmented PDO".
+pub struct EprAdjustableVoltageSupplyPdo {
+    /// 1 W units.
+    pub pdp: crate::Milliwatt,
+    /// Peak Current, as a 2-bit code (same encoding as the Fixed Supply
+    /// PDO's Peak Current field).
+    pub peak_current: u8,
+    /// 100 mV units.
+    pub max_voltage: crate::Millivolt,
+    /// 100 mV units.
+    pub min_voltage: crate::Millivolt,
+}
+
+impl FromBytes for EprAdjustableVoltageSupplyPdo {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let pdp = (reader.read::<u32>(8)? * 1000).into();
+        let peak_current = reader.read::<u8>(2)?;
+        let max_voltage = (reader.read::<u32>(9)? * 100).into();
+        let _reserved = reader.read_bit()?;
+        let min_voltage = (reader.read::<u32>(8)? * 100).into();
+
+        Ok(Self {
+            pdp,
+            peak_current,
+            max_voltage,
+            min_voltage,
+        })
+    }
+}
+
+impl ToBytes for EprAdjustableVoltageSupplyPdo {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write(8, self.pdp.0 / 1000)?;
+        bit_writer.write(2, self.peak_current)?;
+        bit_writer.write(9, self.max_voltage.0 / 100)?;
+        bit_writer.write_bit(false)?;
+        bit_writer.write(8, self.min_voltage.0 / 100)?;
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, CApiWrapper)]
 #[c_api(prefix = "Pd", repr_c = true)]
 pub enum Pdo {
-    #[c_api(variant_prefix = "Pd3p2")]
+    #[c_api(variant_prefix = "Pd3p2", tag = 0)]
     Pd3p2FixedSupplyPdo(FixedSupplyPdo),
-    #[c_api(variant_prefix = "Pd3p2")]
+    #[c_api(variant_prefix = "Pd3p2", tag = 1)]
     Pd3p2BatterySupplyPdo(BatterySupplyPdo),
-    #[c_api(variant_prefix = "Pd3p2")]
+    #[c_api(variant_prefix = "Pd3p2", tag = 2)]
     Pd3p2VariableSupplyPdo(VariableSupplyPdo),
-    #[c_api(variant_prefix = "Pd3p2")]
+    /// SPR Programmable Power Supply (APDO subtype `00`).
+    #[c_api(variant_prefix = "Pd3p2", tag = 3)]
     Pd3p2AugmentedPdo(SprProgrammableSupplyPdo),
+    /// SPR Adjustable Voltage Supply (APDO subtype `01`).
+    #[c_api(variant_prefix = "Pd3p2", tag = 4)]
+    Pd3p2SprAvsPdo(SprAdjustableVoltageSupplyPdo),
+    /// EPR Adjustable Voltage Supply (APDO subtype `11`).
+    #[c_api(variant_prefix = "Pd3p2", tag = 5)]
+    Pd3p2EprAvsPdo(EprAdjustableVoltageSupplyPdo),
 }
 
 impl Pdo {
@@ -124,7 +240,7 @@ impl Pdo {
                 _ => Err(Error::UnsupportedUsbRevision {
                     revision,
                     #[cfg(feature = "backtrace")]
-                    backtrace: std::backtrace::Backtrace::capture(),
+                    backtrace: crate::backtrace::Backtrace::capture(),
                 }),
             },
             1 => match revision.0 {
@@ -135,7 +251,7 @@ impl Pdo {
                 _ => Err(Error::UnsupportedUsbRevision {
                     revision,
                     #[cfg(feature = "backtrace")]
-                    backtrace: std::backtrace::Backtrace::capture(),
+                    backtrace: crate::backtrace::Backtrace::capture(),
                 }),
             },
             2 => match revision.0 {
@@ -146,30 +262,416 @@ impl Pdo {
                 _ => Err(Error::UnsupportedUsbRevision {
                     revision,
                     #[cfg(feature = "backtrace")]
-                    backtrace: std::backtrace::Backtrace::capture(),
+                    backtrace: crate::backtrace::Backtrace::capture(),
                 }),
             },
             3 => match revision.0 {
                 0x310 => {
-                    let pdo = SprProgrammableSupplyPdo::from_bytes(reader)?;
-                    Ok(Pdo::Pd3p2AugmentedPdo(pdo))
+                    // bits29..28: the APDO subtype selector.
+                    let apdo_subtype = reader.read::<u32>(2)?;
+                    match apdo_subtype {
+                        0 => {
+                            let pdo = SprProgrammableSupplyPdo::from_bytes(reader)?;
+                            Ok(Pdo::Pd3p2AugmentedPdo(pdo))
+                        }
+                        1 => {
+                            let pdo = SprAdjustableVoltageSupplyPdo::from_bytes(reader)?;
+                            Ok(Pdo::Pd3p2SprAvsPdo(pdo))
+                        }
+                        3 => {
+                            let pdo = EprAdjustableVoltageSupplyPdo::from_bytes(reader)?;
+                            Ok(Pdo::Pd3p2EprAvsPdo(pdo))
+                        }
+                        other => Err(Error::ParseError {
+                            field: "apdo_subtype (i.e.: bits29..28)".into(),
+                            value: other,
+                            #[cfg(feature = "backtrace")]
+                            backtrace: crate::backtrace::Backtrace::capture(),
+                        }),
+                    }
                 }
                 _ => Err(Error::UnsupportedUsbRevision {
                     revision,
                     #[cfg(feature = "backtrace")]
-                    backtrace: std::backtrace::Backtrace::capture(),
+                    backtrace: crate::backtrace::Backtrace::capture(),
                 }),
             },
             other => Err(Error::ParseError {
                 field: "pdo_type (i.e.: bits31..30)".into(),
                 value: other,
                 #[cfg(feature = "backtrace")]
-                backtrace: std::backtrace::Backtrace::capture(),
+                backtrace: crate::backtrace::Backtrace::capture(),
             }),
         }
     }
 }
 
+impl Pdo {
+    /// Builds a `Pd3p2FixedSupplyPdo` from a list of `(Conversion, raw bytes)`
+    /// pairs describing the `voltage` and `operational_current` fields, in
+    /// that order. This lets a front-end ingesting a non-native capture
+    /// format (an analyzer CSV, a sysfs dump with unusual units) describe
+    /// each field's conversion declaratively instead of open-coding bit
+    /// reads.
+    pub fn from_fields(conversions: &[(crate::conversion::Conversion, &[u8])]) -> Result<Self> {
+        use crate::conversion::ConvertedValue;
+
+        let field = |index: usize, field_name: &str| -> Result<u32> {
+            let (conversion, bytes) = conversions.get(index).ok_or_else(|| Error::ParseError {
+                field: field_name.to_string(),
+                value: 0,
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            })?;
+            match conversion.convert(bytes) {
+                Ok(ConvertedValue::Millivolt(v)) => Ok(v.0),
+                Ok(ConvertedValue::Milliamp(v)) => Ok(v.0),
+                Ok(ConvertedValue::Int(v)) => Ok(v as u32),
+                _ => Err(Error::ParseStringError {
+                    field: field_name.to_string(),
+                    value: format!("{conversion:?}"),
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                }),
+            }
+        };
+
+        let voltage = field(0, "voltage")?.into();
+        let operational_current = field(1, "operational_current")?.into();
+
+        Ok(Pdo::Pd3p2FixedSupplyPdo(FixedSupplyPdo {
+            voltage,
+            operational_current,
+            ..Default::default()
+        }))
+    }
+}
+
+impl ToBytes for Pdo {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        // See USB PD 3.2. - Table 6.7 "Power Data Object"
+        match self {
+            Pdo::Pd3p2FixedSupplyPdo(pdo) => {
+                bit_writer.write(2, 0u32)?;
+                pdo.to_bytes(bit_writer)
+            }
+            Pdo::Pd3p2BatterySupplyPdo(pdo) => {
+                bit_writer.write(2, 1u32)?;
+                pdo.to_bytes(bit_writer)
+            }
+            Pdo::Pd3p2VariableSupplyPdo(pdo) => {
+                bit_writer.write(2, 2u32)?;
+                pdo.to_bytes(bit_writer)
+            }
+            Pdo::Pd3p2AugmentedPdo(pdo) => {
+                bit_writer.write(2, 3u32)?;
+                bit_writer.write(2, 0u32)?;
+                pdo.to_bytes(bit_writer)
+            }
+            Pdo::Pd3p2SprAvsPdo(pdo) => {
+                bit_writer.write(2, 3u32)?;
+                bit_writer.write(2, 1u32)?;
+                pdo.to_bytes(bit_writer)
+            }
+            Pdo::Pd3p2EprAvsPdo(pdo) => {
+                bit_writer.write(2, 3u32)?;
+                bit_writer.write(2, 3u32)?;
+                pdo.to_bytes(bit_writer)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// Reads a 32-bit PDO, re-encodes it, and asserts the bits match.
+    fn round_trip(bytes: [u8; 4], revision: BcdWrapper) {
+        let mut reader = BitReader::new(Cursor::new(&bytes[..]));
+        let pdo = Pdo::from_bytes(&mut reader, revision).unwrap();
+
+        let mut encoded = [0u8; 4];
+        let mut writer = BitWriter::new(Cursor::new(&mut encoded[..]));
+        pdo.to_bytes(&mut writer).unwrap();
+        writer.byte_align().unwrap();
+
+        assert_eq!(bytes, encoded);
+    }
+
+    #[test]
+    fn round_trip_fixed_supply_pdo() {
+        round_trip([0b0000_0000, 0b0000_0000, 0b0000_0000, 0b0000_0000], BcdWrapper(0x310));
+    }
+
+    #[test]
+    fn from_bytes_rejects_unsupported_revision() {
+        let bytes = [0u8; 4];
+        let mut reader = BitReader::new(Cursor::new(&bytes[..]));
+        let err = Pdo::from_bytes(&mut reader, BcdWrapper(0x200)).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedUsbRevision { .. }));
+    }
+
+    #[test]
+    fn decodes_request_data_object() {
+        // Hand-encode a Request Data Object per USB PD Specification section
+        // 6.4.2, table 6-22, matching the field widths `from_bytes` expects:
+        // object position (4), 6 single-bit flags, 1 reserved bit, operating
+        // current (11 bits, in 10mA units) then max operating current (10
+        // bits, in 10mA units) — 32 bits total.
+        let mut encoded = [0u8; 4];
+        let mut writer = BitWriter::new(Cursor::new(&mut encoded[..]));
+        writer.write::<u8>(4, 5).unwrap(); // object_position
+        writer.write_bit(true).unwrap(); // give_back
+        writer.write_bit(false).unwrap(); // capability_mismatch
+        writer.write_bit(true).unwrap(); // usb_communications_capable
+        writer.write_bit(false).unwrap(); // no_usb_suspend
+        writer.write_bit(true).unwrap(); // unchunked_extended_messages_supported
+        writer.write_bit(false).unwrap(); // epr_mode_capable
+        writer.write_bit(false).unwrap(); // reserved
+        writer.write::<u32>(11, 150).unwrap(); // operating_current: 1500 mA
+        writer.write::<u32>(10, 300).unwrap(); // max_operating_current: 3000 mA
+        writer.byte_align().unwrap();
+
+        let mut reader = BitReader::new(Cursor::new(&encoded[..]));
+        let rdo = RequestDataObject::from_bytes(&mut reader).unwrap();
+
+        assert_eq!(rdo.object_position, 5);
+        assert!(rdo.give_back);
+        assert!(!rdo.capability_mismatch);
+        assert!(rdo.usb_communications_capable);
+        assert!(!rdo.no_usb_suspend);
+        assert!(rdo.unchunked_extended_messages_supported);
+        assert!(!rdo.epr_mode_capable);
+        assert_eq!(rdo.operating_current, crate::Milliamp(1500));
+        assert_eq!(rdo.max_operating_current, crate::Milliamp(3000));
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default, N)]
+/// The two-bit spec revision field carried by every [`PdHeader`].
+pub enum PdSpecRevision {
+    #[default]
+    V1_0,
+    V2_0,
+    V3_0,
+    Reserved,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+/// The 16-bit header prefixed to every USB PD message. See section 6.2.1.1
+/// ("Message Header") of the USB PD Specification.
+///
+/// `number_of_data_objects` being zero is what distinguishes a Control
+/// message from a Data message: a Control message carries no data objects,
+/// while a Data message is followed by that many 32-bit data objects.
+pub struct PdHeader {
+    /// Identifies the control or data message, depending on
+    /// `number_of_data_objects`. See [`ControlMessageType`]/
+    /// [`DataMessageType`].
+    pub message_type: u8,
+    pub port_data_role: bool,
+    pub spec_revision: PdSpecRevision,
+    /// Port Power Role for SOP messages, Cable Plug for SOP'/SOP'' messages.
+    pub port_power_role_or_cable_plug: bool,
+    pub message_id: u8,
+    pub number_of_data_objects: u8,
+    pub extended: bool,
+}
+
+impl FromBytes for PdHeader {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        // See USB PD 3.2. - Section 6.2.1.1 "Message Header". Read MSB
+        // first, i.e. bit15 (Extended) down to bit0 (Message Type), matching
+        // the repo's established bit-order convention (see `Pdo::from_bytes`,
+        // `VdmHeader::from_bytes`).
+        let extended = reader.read_bit()?;
+        let number_of_data_objects = reader.read::<u8>(3)?;
+        let message_id = reader.read::<u8>(3)?;
+        let port_power_role_or_cable_plug = reader.read_bit()?;
+        let spec_revision_value = reader.read::<u32>(2)?;
+        let spec_revision = match spec_revision_value {
+            0 => PdSpecRevision::V1_0,
+            1 => PdSpecRevision::V2_0,
+            2 => PdSpecRevision::V3_0,
+            _ => PdSpecRevision::Reserved,
+        };
+        let port_data_role = reader.read_bit()?;
+        let message_type = reader.read::<u8>(5)?;
+
+        Ok(Self {
+            message_type,
+            port_data_role,
+            spec_revision,
+            port_power_role_or_cable_plug,
+            message_id,
+            number_of_data_objects,
+            extended,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+/// The 16-bit header that follows the main [`PdHeader`] on any Extended
+/// message (i.e. whenever `PdHeader::extended` is set). See section 6.2.1.2
+/// of the USB PD Specification.
+pub struct ExtendedMessageHeader {
+    /// The total number of payload bytes in the (possibly multi-chunk)
+    /// message that follows.
+    pub data_size: u32,
+    /// Set when the payload doesn't fit in a single chunk, so this message
+    /// is part of a chunked transfer.
+    pub chunked: bool,
+    /// This chunk's sequence number within the transfer, starting at 0.
+    pub chunk_number: u32,
+    /// Set by the message requester to ask the responder for the next
+    /// chunk.
+    pub request_chunk: bool,
+}
+
+impl FromBytes for ExtendedMessageHeader {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let _reserved = reader.read_bit()?; // bit15: reserved
+        let chunked = reader.read_bit()?; // bit14
+        let chunk_number = reader.read::<u32>(4)?; // bits13..10
+        let request_chunk = reader.read_bit()?; // bit9
+        let data_size = reader.read::<u32>(9)?; // bits8..0
+
+        Ok(Self {
+            data_size,
+            chunked,
+            chunk_number,
+            request_chunk,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default, N, Copy)]
+/// An Extended message's type, i.e. its `message_type` when
+/// [`PdHeader::extended`] is set. See table 6-6 of the USB PD Specification.
+pub enum ExtendedMessageType {
+    #[default]
+    Reserved = 0,
+    SourceCapabilitiesExtended = 1,
+    Status = 2,
+    GetBatteryCap = 3,
+    GetBatteryStatus = 4,
+    BatteryCapabilities = 5,
+    GetManufacturerInfo = 6,
+    ManufacturerInfo = 7,
+    SecurityRequest = 8,
+    SecurityResponse = 9,
+    FirmwareUpdateRequest = 10,
+    FirmwareUpdateResponse = 11,
+    PpsStatus = 12,
+    CountryInfo = 13,
+    CountryCodes = 14,
+    SinkCapabilitiesExtended = 16,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default, N, Copy)]
+/// A Control message's type, i.e. its `message_type` when
+/// [`PdHeader::number_of_data_objects`] is 0. See table 6-5 of the USB PD
+/// Specification.
+pub enum ControlMessageType {
+    #[default]
+    Reserved = 0,
+    GoodCrc = 1,
+    GotoMin = 2,
+    Accept = 3,
+    Reject = 4,
+    Ping = 5,
+    PsRdy = 6,
+    GetSourceCap = 7,
+    GetSinkCap = 8,
+    DrSwap = 9,
+    PrSwap = 10,
+    VconnSwap = 11,
+    Wait = 12,
+    SoftReset = 13,
+    DataReset = 14,
+    DataResetComplete = 15,
+    NotSupported = 16,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default, N, Copy)]
+/// A Data message's type, i.e. its `message_type` when
+/// [`PdHeader::number_of_data_objects`] is non-zero. See table 6-6 of the USB
+/// PD Specification.
+pub enum DataMessageType {
+    #[default]
+    Reserved = 0,
+    SourceCapabilities = 1,
+    Request = 2,
+    Bist = 3,
+    SinkCapabilities = 4,
+    BatteryStatus = 5,
+    Alert = 6,
+    GetCountryInfo = 7,
+    EnterUsb = 8,
+    EprRequest = 9,
+    EprMode = 10,
+    SourceInfo = 11,
+    Revision = 12,
+    VendorDefined = 15,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default, CApiWrapper)]
+#[c_api(prefix = "Pd", repr_c = true)]
+/// A Request Data Object (Data Message). See section 6.4.2 of the USB PD
+/// Specification.
+pub struct RequestDataObject {
+    pub object_position: u8,
+    pub give_back: bool,
+    pub capability_mismatch: bool,
+    pub usb_communications_capable: bool,
+    pub no_usb_suspend: bool,
+    pub unchunked_extended_messages_supported: bool,
+    pub epr_mode_capable: bool,
+    pub operating_current: crate::Milliamp,
+    pub max_operating_current: crate::Milliamp,
+}
+
+impl FromBytes for RequestDataObject {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let object_position = reader.read::<u8>(4)?;
+        let give_back = reader.read_bit()?;
+        let capability_mismatch = reader.read_bit()?;
+        let usb_communications_capable = reader.read_bit()?;
+        let no_usb_suspend = reader.read_bit()?;
+        let unchunked_extended_messages_supported = reader.read_bit()?;
+        let epr_mode_capable = reader.read_bit()?;
+        let _reserved = reader.read_bit()?;
+        // Operating Current is an 11-bit field (bits 20..10); Maximum
+        // Operating Current is 10 bits (bits 9..0) — see USB PD
+        // Specification section 6.4.2, table 6-22.
+        let operating_current = (reader.read::<u32>(11)? * 10).into();
+        let max_operating_current = (reader.read::<u32>(10)? * 10).into();
+
+        Ok(Self {
+            object_position,
+            give_back,
+            capability_mismatch,
+            usb_communications_capable,
+            no_usb_suspend,
+            unchunked_extended_messages_supported,
+            epr_mode_capable,
+            operating_current,
+            max_operating_current,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, CApiWrapper)]
 #[c_api(prefix = "Pd", repr_c = true)]
 pub enum Message {
@@ -191,9 +693,17 @@ pub enum Message {
     /// Revision (Data Message)
     #[c_api(variant_prefix = "Pd3p2")]
     Pd3p2Revision(RevisionMessageData),
+    /// A Control message carrying no data objects, e.g. GoodCRC, Accept,
+    /// Reject, Ping, PS_RDY, Get_Source_Cap, Get_Sink_Cap, Soft_Reset.
+    #[c_api(variant_prefix = "Pd3p2")]
+    Pd3p2Control(ControlMessageType),
+    /// Request (Data Message)
+    #[c_api(variant_prefix = "Pd3p2")]
+    Pd3p2Request(RequestDataObject),
 }
 
 /// This enum represents the recipient of the PD message.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Default, N, Copy, CApiWrapper)]
 #[c_api(prefix = "Pd", repr_c = true)]
 pub enum PdMessageRecipient {
@@ -213,6 +723,7 @@ pub enum PdMessageRecipient {
 }
 
 /// This enum represents the type of the PD response message.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Default, N, Copy, CApiWrapper)]
 #[c_api(prefix = "Pd", repr_c = true)]
 pub enum PdMessageResponseType {
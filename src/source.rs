@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! An abstraction over where PD/VDO decode input comes from.
+//!
+//! The decode logic in [`crate::pd`] and [`crate::vdo`] only needs a handful
+//! of typed values fetched by key; it shouldn't have to care whether those
+//! values came from a sysfs attribute file, a register read on an embedded
+//! USB-PD controller, or a captured byte stream. [`SysfsBackend`] is one
+//! implementor of this trait, not the only conceivable one — a `no_std`
+//! embedded backend could implement it directly against a register map
+//! without pulling in `std::fs`/`std::path`.
+//!
+//! [`SysfsBackend`]: crate::backends::sysfs::SysfsBackend
+
+use std::io::Cursor;
+
+use crate::pd::Pd3p2DiscoverIdentityResponse;
+use crate::vdo::Pd3p2CertStatVdo;
+use crate::vdo::Pd3p2IdHeaderVdo;
+use crate::vdo::Pd3p2ProductTypeVdo;
+use crate::vdo::Pd3p2ProductVdo;
+use crate::BcdWrapper;
+use crate::BitReader;
+use crate::FromBytes;
+use crate::Result;
+
+/// A keyed source of typed Type-C/PD values.
+pub trait TypecSource {
+    /// Reads the value at `key` as an unsigned 32-bit integer.
+    fn read_u32(&mut self, key: &str) -> Result<u32>;
+
+    /// Reads the value at `key` as a boolean.
+    fn read_bit(&mut self, key: &str) -> Result<bool>;
+
+    /// Reads the value at `key` as a BCD-encoded revision.
+    fn read_bcd(&mut self, key: &str) -> Result<BcdWrapper>;
+
+    /// Reads the Discover Identity response rooted at `key`: the Cert Stat,
+    /// ID Header and Product VDOs, followed by up to three Product Type
+    /// VDOs.
+    fn read_identity(&mut self, key: &str) -> Result<(u32, u32, u32, [Pd3p2ProductTypeVdo; 3])>;
+}
+
+/// Decodes a Discover Identity response rooted at `key`, from any
+/// [`TypecSource`] — sysfs today, potentially a register map on a `no_std`
+/// embedded backend tomorrow. [`crate::backends::sysfs::sysfs_reader::SysfsReader::discover_identity`]
+/// is this function's sysfs-backed caller.
+pub fn discover_identity<T: TypecSource>(
+    source: &mut T,
+    key: &str,
+) -> Result<Pd3p2DiscoverIdentityResponse> {
+    let (cert_stat, id_header, product, product_type_vdo) = source.read_identity(key)?;
+
+    let binding = id_header.to_le_bytes();
+    let mut br = BitReader::new(Cursor::new(&binding));
+    let id_header_vdo = Pd3p2IdHeaderVdo::from_bytes(&mut br)?;
+
+    let binding = cert_stat.to_le_bytes();
+    let mut br = BitReader::new(Cursor::new(&binding));
+    let cert_stat = Pd3p2CertStatVdo::from_bytes(&mut br)?;
+
+    let binding = product.to_le_bytes();
+    let mut br = BitReader::new(Cursor::new(&binding));
+    let product_vdo = Pd3p2ProductVdo::from_bytes(&mut br)?;
+
+    Ok(Pd3p2DiscoverIdentityResponse {
+        header: Default::default(),
+        id_header_vdo,
+        cert_stat,
+        product_vdo,
+        product_type_vdo,
+    })
+}
@@ -0,0 +1,712 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The library's high-level, backend-agnostic entry point.
+
+use crate::backends::sysfs::SysfsBackend;
+use crate::pd::PdPdo;
+use crate::ucsi::GetAlternateModesRecipient;
+use crate::ucsi::GetPdoSourceCapabilitiesType;
+use crate::ucsi::GetPdosSrcOrSink;
+use crate::ucsi::PdMessage;
+use crate::ucsi::PdMessageRecipient;
+use crate::ucsi::PdMessageResponseType;
+use crate::ucsi::UcsiAlternateMode;
+use crate::ucsi::UcsiCableProperty;
+use crate::ucsi::UcsiCapability;
+use crate::ucsi::UcsiConnectorCapability;
+use crate::ucsi::UcsiConnectorStatus;
+use crate::vdo::CableIdentity;
+use crate::BcdWrapper;
+use crate::Error;
+use crate::OsBackend;
+use crate::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The platform backends [`TypecRs::new`] knows how to construct.
+pub enum OsBackends {
+    /// Reads port, partner and cable state from the kernel's `typec` and
+    /// `power_supply` sysfs classes.
+    Sysfs,
+    /// Talks to the kernel's UCSI interface directly via its `/dev`
+    /// character device. Not implemented yet; constructing it returns
+    /// [`Error::NotSupported`].
+    LinuxUcsi,
+}
+
+impl std::str::FromStr for OsBackends {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "sysfs" => Ok(OsBackends::Sysfs),
+            "linux-ucsi" => Ok(OsBackends::LinuxUcsi),
+            _ => Err(format!("unknown backend {s:?} (expected \"sysfs\" or \"linux-ucsi\")")),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Governs how [`TypecRs`] reacts to a recoverable backend error (see
+/// [`Error::is_recoverable`]): it re-issues the same command up to
+/// `max_retries` more times before giving up and returning the error to
+/// the caller. Errors that aren't recoverable (e.g. [`Error::NotSupported`])
+/// are always returned immediately, regardless of this policy, matching
+/// how a PD controller driver distinguishes transient bus errors from a
+/// genuinely unsupported command.
+pub struct RetryPolicy {
+    /// How many additional attempts to make after the first failure.
+    pub max_retries: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_retries: 3 }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Governs whether [`TypecRs::with_retries_and_reset`] is allowed to
+/// escalate to a PD reset once [`RetryPolicy::max_retries`] is exhausted,
+/// mirroring the automatic hard-reset/soft-reset/retry behavior sink-side PD
+/// stacks (e.g. the FUSB302B driver) run by default. Left disabled by
+/// default because issuing a reset is a much more disruptive recovery step
+/// than a plain retry, and not every PPM reports
+/// [`UcsiBmOptionalFeatures::pd_reset_notification_supported`](crate::ucsi::UcsiBmOptionalFeatures::pd_reset_notification_supported),
+/// so callers opt in deliberately.
+pub struct ResetEscalationPolicy {
+    /// Whether [`TypecRs::with_retries_and_reset`] may escalate to a reset
+    /// at all. Even when `true`, escalation only happens if the PPM
+    /// advertises `pd_reset_notification_supported`.
+    pub enabled: bool,
+}
+
+impl Default for ResetEscalationPolicy {
+    fn default() -> Self {
+        Self { enabled: false }
+    }
+}
+
+/// The library's main entry point: a thin, backend-agnostic facade over
+/// whichever [`OsBackend`] implementation [`TypecRs::new`] managed to
+/// construct. Every method simply forwards to the underlying backend
+/// (retrying on recoverable errors per [`TypecRs::retry_policy`]), except
+/// for the aggregations (like [`TypecRs::pd_capabilities`]) that are
+/// assembled once here from several backend calls instead of being
+/// duplicated in every [`OsBackend`] implementation.
+pub struct TypecRs {
+    backend: Box<dyn OsBackend>,
+    retry_policy: RetryPolicy,
+    reset_policy: ResetEscalationPolicy,
+}
+
+impl TypecRs {
+    /// Constructs a [`TypecRs`] backed by `backend`, with the default
+    /// [`RetryPolicy`]. Returns [`Error::NotSupported`] if `backend` isn't
+    /// available on this build.
+    pub fn new(backend: OsBackends) -> Result<Self> {
+        Self::with_retry_policy(backend, RetryPolicy::default())
+    }
+
+    /// Like [`TypecRs::new`], but with a caller-supplied [`RetryPolicy`]
+    /// instead of the default.
+    pub fn with_retry_policy(backend: OsBackends, retry_policy: RetryPolicy) -> Result<Self> {
+        let backend: Box<dyn OsBackend> = match backend {
+            OsBackends::Sysfs => Box::new(SysfsBackend::new()?),
+            OsBackends::LinuxUcsi => {
+                return Err(Error::NotSupported {
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                })
+            }
+        };
+
+        Ok(Self {
+            backend,
+            retry_policy,
+            reset_policy: ResetEscalationPolicy::default(),
+        })
+    }
+
+    /// The retry policy currently in effect.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// Replaces the retry policy currently in effect.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// The reset escalation policy currently in effect.
+    pub fn reset_policy(&self) -> ResetEscalationPolicy {
+        self.reset_policy
+    }
+
+    /// Replaces the reset escalation policy currently in effect.
+    pub fn set_reset_policy(&mut self, reset_policy: ResetEscalationPolicy) {
+        self.reset_policy = reset_policy;
+    }
+
+    /// Runs `f` against the backend, re-issuing it up to
+    /// `self.retry_policy.max_retries` more times as long as it keeps
+    /// failing with a recoverable error (see [`Error::is_recoverable`]).
+    /// Any other error, or exhausting the retries, is returned as-is.
+    fn with_retries<T>(&mut self, mut f: impl FnMut(&mut dyn OsBackend) -> Result<T>) -> Result<T> {
+        let mut attempt = 0;
+        loop {
+            match f(self.backend.as_mut()) {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < self.retry_policy.max_retries && e.is_recoverable() => {
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Whether the PPM advertises `pd_reset_notification_supported`, i.e.
+    /// whether it's safe for [`TypecRs::with_retries_and_reset`] to escalate
+    /// to a reset at all.
+    fn pd_reset_notification_supported(&mut self) -> Result<bool> {
+        Ok(self
+            .with_retries(|backend| backend.capabilities())?
+            .bm_optional_features
+            .pd_reset_notification_supported)
+    }
+
+    /// Like [`TypecRs::with_retries`], but on top of plain retries, also
+    /// supports escalating to a PD reset on `connector_nr` when
+    /// [`TypecRs::reset_policy`] is enabled: once retries are exhausted and
+    /// `f` is still failing with a recoverable error, issues a soft reset
+    /// and retries `f` again; if that also keeps failing, issues a hard
+    /// reset and gives `f` one last round of retries before giving up.
+    /// Escalation is skipped (falling straight through to plain retries)
+    /// if the PPM doesn't advertise
+    /// [`UcsiBmOptionalFeatures::pd_reset_notification_supported`](crate::ucsi::UcsiBmOptionalFeatures::pd_reset_notification_supported)
+    /// or `connector_reset` isn't supported by the backend. `on_pd_reset` is
+    /// called with `false`/`true` (soft/hard) each time a reset is actually
+    /// issued, so callers can hook into PD Reset notifications instead of
+    /// only observing the final result.
+    pub fn with_retries_and_reset<T>(
+        &mut self,
+        connector_nr: usize,
+        mut on_pd_reset: impl FnMut(bool),
+        mut f: impl FnMut(&mut dyn OsBackend) -> Result<T>,
+    ) -> Result<T> {
+        let err = match self.with_retries(&mut f) {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+
+        if !self.reset_policy.enabled || !err.is_recoverable() || !self.pd_reset_notification_supported()? {
+            return Err(err);
+        }
+
+        // A reset is only a recovery *attempt*: if the backend can't issue
+        // one (e.g. `Error::NotSupported`, since the `typec` sysfs class has
+        // no reset attribute), there's nothing left to escalate to, so the
+        // original recoverable error is what the caller needs to see, not
+        // connector_reset's own error masking it.
+        if self.with_retries(|backend| backend.connector_reset(connector_nr, false)).is_err() {
+            return Err(err);
+        }
+        on_pd_reset(false);
+
+        let err = match self.with_retries(&mut f) {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+
+        if !err.is_recoverable() {
+            return Err(err);
+        }
+
+        if self.with_retries(|backend| backend.connector_reset(connector_nr, true)).is_err() {
+            return Err(err);
+        }
+        on_pd_reset(true);
+
+        self.with_retries(&mut f)
+    }
+
+    pub fn capabilities(&mut self) -> Result<UcsiCapability> {
+        self.with_retries(|backend| backend.capabilities())
+    }
+
+    pub fn connector_capabilties(&mut self, connector_nr: usize) -> Result<UcsiConnectorCapability> {
+        self.with_retries(|backend| backend.connector_capabilties(connector_nr))
+    }
+
+    pub fn alternate_modes(
+        &mut self,
+        recipient: GetAlternateModesRecipient,
+        connector_nr: usize,
+    ) -> Result<Vec<UcsiAlternateMode>> {
+        self.with_retries(|backend| backend.alternate_modes(recipient, connector_nr))
+    }
+
+    pub fn cable_properties(&mut self, connector_nr: usize) -> Result<UcsiCableProperty> {
+        self.with_retries(|backend| backend.cable_properties(connector_nr))
+    }
+
+    pub fn cable_identity(&mut self, connector_nr: usize) -> Result<CableIdentity> {
+        self.with_retries(|backend| backend.cable_identity(connector_nr))
+    }
+
+    pub fn connector_status(&mut self, connector_nr: usize) -> Result<UcsiConnectorStatus> {
+        self.with_retries(|backend| backend.connector_status(connector_nr))
+    }
+
+    pub fn pd_message(
+        &mut self,
+        connector_nr: usize,
+        recipient: PdMessageRecipient,
+        response_type: PdMessageResponseType,
+    ) -> Result<PdMessage> {
+        self.with_retries_and_reset(
+            connector_nr,
+            |_hard_reset| {},
+            |backend| backend.pd_message(connector_nr, recipient, response_type),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn pdos(
+        &mut self,
+        connector_nr: usize,
+        partner_pdo: bool,
+        pdo_offset: u32,
+        nr_pdos: usize,
+        src_or_sink_pdos: GetPdosSrcOrSink,
+        pdo_type: GetPdoSourceCapabilitiesType,
+        revision: BcdWrapper,
+    ) -> Result<Vec<PdPdo>> {
+        self.with_retries(|backend| {
+            backend.pdos(
+                connector_nr,
+                partner_pdo,
+                pdo_offset,
+                nr_pdos,
+                src_or_sink_pdos,
+                pdo_type,
+                revision,
+            )
+        })
+    }
+
+    pub fn set_power_role(&mut self, connector_nr: usize, role: crate::ucsi::PowerRole) -> Result<()> {
+        self.with_retries(|backend| backend.set_power_role(connector_nr, role))
+    }
+
+    pub fn set_data_role(&mut self, connector_nr: usize, role: crate::ucsi::DataRole) -> Result<()> {
+        self.with_retries(|backend| backend.set_data_role(connector_nr, role))
+    }
+
+    pub fn set_usb_operation_mode(
+        &mut self,
+        connector_nr: usize,
+        mode: crate::ucsi::ConnectorCapabilityOperationMode,
+    ) -> Result<()> {
+        self.with_retries(|backend| backend.set_usb_operation_mode(connector_nr, mode))
+    }
+
+    pub fn set_alternate_mode(&mut self, connector_nr: usize, alt_mode_nr: usize, enter: bool) -> Result<()> {
+        self.with_retries(|backend| backend.set_alternate_mode(connector_nr, alt_mode_nr, enter))
+    }
+
+    /// Reads a connector's (or its partner's, when `partner` is true) full
+    /// PD capability profile: its source and sink PDO lists, each already
+    /// classified by [`PdPdo`]'s own variants (Fixed/Battery/Variable/
+    /// Augmented/PPS), together with the negotiated PD revision. This is
+    /// the aggregation that used to live in the `lstypec` binary as four
+    /// separate [`TypecRs::pdos`] calls with manual [`Error::NotSupported`]
+    /// handling for each.
+    pub fn pd_capabilities(&mut self, connector_nr: usize, partner: bool) -> Result<PdCapabilities> {
+        let revision = self.with_retries(|backend| backend.capabilities())?.pd_version;
+
+        let source = match self.with_retries(|backend| {
+            backend.pdos(
+                connector_nr,
+                partner,
+                0,
+                0,
+                GetPdosSrcOrSink::Source,
+                GetPdoSourceCapabilitiesType::CurrentSupportedSourceCapabilities,
+                revision,
+            )
+        }) {
+            Ok(pdos) => pdos,
+            Err(Error::NotSupported { .. }) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+
+        let sink = match self.with_retries(|backend| {
+            backend.pdos(
+                connector_nr,
+                partner,
+                0,
+                0,
+                GetPdosSrcOrSink::Sink,
+                GetPdoSourceCapabilitiesType::CurrentSupportedSourceCapabilities,
+                revision,
+            )
+        }) {
+            Ok(pdos) => pdos,
+            Err(Error::NotSupported { .. }) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(PdCapabilities {
+            source,
+            sink,
+            revision,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// A connector's (or its partner's) full USB PD capability profile, as
+/// assembled by [`TypecRs::pd_capabilities`]: the source and sink PDO
+/// lists read separately from the platform backend, together with the PD
+/// revision they were negotiated under.
+pub struct PdCapabilities {
+    /// The PDOs this connector (or partner) offers as a source, classified
+    /// by their own [`PdPdo`] variant (Fixed/Battery/Variable/Augmented/
+    /// PPS). Empty if the backend doesn't support reading source PDOs.
+    pub source: Vec<PdPdo>,
+    /// The PDOs this connector (or partner) offers as a sink, classified
+    /// the same way. Empty if the backend doesn't support reading sink
+    /// PDOs.
+    pub sink: Vec<PdPdo>,
+    /// The USB Power Delivery Specification revision these PDOs were
+    /// decoded against.
+    pub revision: BcdWrapper,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vdo::CableIdentity;
+
+    /// An [`OsBackend`] whose [`FakeBackend::capabilities`] fails with a
+    /// recoverable [`Error::IoError`] `fail_times` times before succeeding,
+    /// so [`TypecRs::with_retries`]'s retry count can be asserted against a
+    /// known number of failures.
+    struct FakeBackend {
+        fail_times: u32,
+        calls: u32,
+    }
+
+    impl FakeBackend {
+        fn new(fail_times: u32) -> Self {
+            Self { fail_times, calls: 0 }
+        }
+
+        fn io_error() -> Error {
+            Error::IoError {
+                source: std::io::Error::new(std::io::ErrorKind::Other, "simulated transient failure"),
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            }
+        }
+    }
+
+    impl OsBackend for FakeBackend {
+        fn capabilities(&mut self) -> Result<UcsiCapability> {
+            self.calls += 1;
+            if self.calls <= self.fail_times {
+                return Err(Self::io_error());
+            }
+            Ok(UcsiCapability::default())
+        }
+
+        fn connector_capabilties(&mut self, _connector_nr: usize) -> Result<UcsiConnectorCapability> {
+            unimplemented!()
+        }
+
+        fn alternate_modes(
+            &mut self,
+            _recipient: GetAlternateModesRecipient,
+            _connector_nr: usize,
+        ) -> Result<Vec<UcsiAlternateMode>> {
+            unimplemented!()
+        }
+
+        fn cable_properties(&mut self, _connector_nr: usize) -> Result<UcsiCableProperty> {
+            unimplemented!()
+        }
+
+        fn cable_identity(&mut self, _connector_nr: usize) -> Result<CableIdentity> {
+            unimplemented!()
+        }
+
+        fn connector_status(&mut self, _connector_nr: usize) -> Result<UcsiConnectorStatus> {
+            unimplemented!()
+        }
+
+        fn pd_message(
+            &mut self,
+            _connector_nr: usize,
+            _recipient: PdMessageRecipient,
+            _response_type: PdMessageResponseType,
+        ) -> Result<PdMessage> {
+            unimplemented!()
+        }
+
+        fn pdos(
+            &mut self,
+            _connector_nr: usize,
+            _partner_pdo: bool,
+            _pdo_offset: u32,
+            _nr_pdos: usize,
+            _src_or_sink_pdos: GetPdosSrcOrSink,
+            _pdo_type: GetPdoSourceCapabilitiesType,
+            _revision: BcdWrapper,
+        ) -> Result<Vec<PdPdo>> {
+            unimplemented!()
+        }
+
+        fn set_power_role(&mut self, _connector_nr: usize, _role: crate::ucsi::PowerRole) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn set_data_role(&mut self, _connector_nr: usize, _role: crate::ucsi::DataRole) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn set_usb_operation_mode(
+            &mut self,
+            _connector_nr: usize,
+            _mode: crate::ucsi::ConnectorCapabilityOperationMode,
+        ) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn set_alternate_mode(&mut self, _connector_nr: usize, _alt_mode_nr: usize, _enter: bool) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn connector_reset(&mut self, _connector_nr: usize, _hard_reset: bool) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    fn typec_rs(backend: FakeBackend, max_retries: u32) -> TypecRs {
+        TypecRs {
+            backend: Box::new(backend),
+            retry_policy: RetryPolicy { max_retries },
+            reset_policy: ResetEscalationPolicy::default(),
+        }
+    }
+
+    /// An [`OsBackend`] for exercising [`TypecRs::with_retries_and_reset`]'s
+    /// escalation order: [`FakeResetBackend::connector_status`] fails with a
+    /// recoverable [`Error::IoError`] until `status_fail_times` calls have
+    /// been made, while [`FakeResetBackend::connector_reset`] records every
+    /// reset it's asked to perform and either honors or rejects it depending
+    /// on `reset_supported`.
+    struct FakeResetBackend {
+        status_calls: u32,
+        status_fail_times: u32,
+        reset_supported: bool,
+        pd_reset_notification_supported: bool,
+        resets: std::rc::Rc<std::cell::RefCell<Vec<bool>>>,
+    }
+
+    impl OsBackend for FakeResetBackend {
+        fn capabilities(&mut self) -> Result<UcsiCapability> {
+            Ok(UcsiCapability {
+                bm_optional_features: crate::ucsi::UcsiBmOptionalFeatures {
+                    pd_reset_notification_supported: self.pd_reset_notification_supported,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        }
+
+        fn connector_capabilties(&mut self, _connector_nr: usize) -> Result<UcsiConnectorCapability> {
+            unimplemented!()
+        }
+
+        fn alternate_modes(
+            &mut self,
+            _recipient: GetAlternateModesRecipient,
+            _connector_nr: usize,
+        ) -> Result<Vec<UcsiAlternateMode>> {
+            unimplemented!()
+        }
+
+        fn cable_properties(&mut self, _connector_nr: usize) -> Result<UcsiCableProperty> {
+            unimplemented!()
+        }
+
+        fn cable_identity(&mut self, _connector_nr: usize) -> Result<CableIdentity> {
+            unimplemented!()
+        }
+
+        fn connector_status(&mut self, _connector_nr: usize) -> Result<UcsiConnectorStatus> {
+            self.status_calls += 1;
+            if self.status_calls <= self.status_fail_times {
+                return Err(FakeBackend::io_error());
+            }
+            Ok(UcsiConnectorStatus::default())
+        }
+
+        fn pd_message(
+            &mut self,
+            _connector_nr: usize,
+            _recipient: PdMessageRecipient,
+            _response_type: PdMessageResponseType,
+        ) -> Result<PdMessage> {
+            unimplemented!()
+        }
+
+        fn pdos(
+            &mut self,
+            _connector_nr: usize,
+            _partner_pdo: bool,
+            _pdo_offset: u32,
+            _nr_pdos: usize,
+            _src_or_sink_pdos: GetPdosSrcOrSink,
+            _pdo_type: GetPdoSourceCapabilitiesType,
+            _revision: BcdWrapper,
+        ) -> Result<Vec<PdPdo>> {
+            unimplemented!()
+        }
+
+        fn set_power_role(&mut self, _connector_nr: usize, _role: crate::ucsi::PowerRole) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn set_data_role(&mut self, _connector_nr: usize, _role: crate::ucsi::DataRole) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn set_usb_operation_mode(
+            &mut self,
+            _connector_nr: usize,
+            _mode: crate::ucsi::ConnectorCapabilityOperationMode,
+        ) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn set_alternate_mode(&mut self, _connector_nr: usize, _alt_mode_nr: usize, _enter: bool) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn connector_reset(&mut self, _connector_nr: usize, hard_reset: bool) -> Result<()> {
+            self.resets.borrow_mut().push(hard_reset);
+            if self.reset_supported {
+                Ok(())
+            } else {
+                Err(Error::NotSupported {
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                })
+            }
+        }
+    }
+
+    fn typec_rs_with_reset(backend: FakeResetBackend) -> TypecRs {
+        TypecRs {
+            backend: Box::new(backend),
+            retry_policy: RetryPolicy { max_retries: 1 },
+            reset_policy: ResetEscalationPolicy { enabled: true },
+        }
+    }
+
+    #[test]
+    fn with_retries_succeeds_after_recoverable_failures_within_budget() {
+        let mut typec = typec_rs(FakeBackend::new(2), 3);
+        typec.capabilities().expect("should succeed within the retry budget");
+    }
+
+    #[test]
+    fn with_retries_gives_up_once_max_retries_is_exhausted() {
+        // One more failure than the retry budget allows: the first attempt
+        // plus `max_retries` retries is 1 + 2 = 3 calls, so a 4th failure
+        // should still be returned as an error instead of being retried.
+        let mut typec = typec_rs(FakeBackend::new(4), 2);
+        let err = typec.capabilities().unwrap_err();
+        assert!(matches!(err, Error::IoError { .. }));
+    }
+
+    #[test]
+    fn with_retries_returns_non_recoverable_errors_immediately() {
+        let mut typec = typec_rs(FakeBackend::new(0), 3);
+        let err = typec
+            .with_retries(|_backend| {
+                Err(Error::NotSupported {
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                })
+            })
+            .unwrap_err();
+        assert!(matches!(err, Error::NotSupported { .. }));
+    }
+
+    #[test]
+    fn with_retries_and_reset_recovers_after_a_soft_reset() {
+        // max_retries is 1, so the initial round is 2 calls; failing both
+        // exhausts it with a recoverable error and triggers escalation.
+        let resets = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut typec = typec_rs_with_reset(FakeResetBackend {
+            status_calls: 0,
+            status_fail_times: 2,
+            reset_supported: true,
+            pd_reset_notification_supported: true,
+            resets: resets.clone(),
+        });
+
+        typec
+            .with_retries_and_reset(0, |_hard_reset| {}, |backend| backend.connector_status(0))
+            .expect("should recover once the soft reset clears the fault");
+        assert_eq!(*resets.borrow(), vec![false]);
+    }
+
+    #[test]
+    fn with_retries_and_reset_escalates_to_a_hard_reset_in_order() {
+        // Two rounds of 2 failing calls each (one before the soft reset, one
+        // after it) exhausts both, so a hard reset should be issued next,
+        // and in that order: soft before hard.
+        let resets = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut typec = typec_rs_with_reset(FakeResetBackend {
+            status_calls: 0,
+            status_fail_times: 4,
+            reset_supported: true,
+            pd_reset_notification_supported: true,
+            resets: resets.clone(),
+        });
+
+        typec
+            .with_retries_and_reset(0, |_hard_reset| {}, |backend| backend.connector_status(0))
+            .expect("should recover once the hard reset clears the fault");
+        assert_eq!(*resets.borrow(), vec![false, true]);
+    }
+
+    #[test]
+    fn with_retries_and_reset_preserves_the_original_error_when_reset_is_unsupported() {
+        // The backend can't actually perform a reset (as is the case for
+        // SysfsBackend, which has no sysfs attribute for it): the caller
+        // should still see the original recoverable error, not
+        // connector_reset's own Error::NotSupported masking it.
+        let resets = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut typec = typec_rs_with_reset(FakeResetBackend {
+            status_calls: 0,
+            status_fail_times: u32::MAX,
+            reset_supported: false,
+            pd_reset_notification_supported: true,
+            resets,
+        });
+
+        let err = typec
+            .with_retries_and_reset(0, |_hard_reset| {}, |backend| backend.connector_status(0))
+            .unwrap_err();
+        assert!(matches!(err, Error::IoError { .. }));
+    }
+}
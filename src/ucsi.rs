@@ -12,6 +12,7 @@ use proc_macros::Snprintf;
 
 use crate::BcdWrapper;
 use crate::BitReader;
+use crate::BitWriter;
 use crate::Error;
 use crate::FromBytes;
 use crate::Result;
@@ -20,6 +21,7 @@ use crate::ToBytes;
 /// See UCSI - Table A-2 Parameter Values
 pub const UCSI_MAX_NUM_ALT_MODE: usize = 128;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Printf, Snprintf, N, Copy)]
 /// See Table 6-24: GET_ALTERNATE_MODES Command.
@@ -33,6 +35,7 @@ pub enum GetAlternateModesRecipient {
     SopDoublePrime = 3,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Printf, Snprintf, N, Copy)]
 pub enum PdoType {
@@ -40,6 +43,7 @@ pub enum PdoType {
     Source,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Printf, Snprintf, N, Copy)]
 pub enum PdoSourceCapabilitiesType {
@@ -48,6 +52,7 @@ pub enum PdoSourceCapabilitiesType {
     MaximumSupportedSourceCapabilities,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Printf, Snprintf)]
 pub enum UcsiCommand {
     /// This command is used to get the PPM capabilities.
@@ -131,6 +136,47 @@ pub enum UcsiCommand {
         /// Response message type.
         message_type: PdMessageResponseType,
     },
+    /// This command resets the PPM. This is the only command that is
+    /// processed even while a command is already in progress.
+    PpmReset,
+    /// This command is used to cancel the last command that was sent.
+    Cancel,
+    /// This command performs a Connector Reset, optionally escalating to a
+    /// Hard Reset.
+    ConnectorReset {
+        /// This field shall be set to the connector being reset.
+        connector_nr: usize,
+        /// When set, a Hard Reset is performed instead of a (data) Reset.
+        hard_reset: bool,
+    },
+    /// This command is used to acknowledge a Connector Change or Command
+    /// Complete notification, so the PPM can raise the next one.
+    AckCcCi {
+        /// Acknowledges the Connector Change notification.
+        connector_change_ack: bool,
+        /// Acknowledges the Command Complete notification.
+        command_complete_ack: bool,
+    },
+    /// This command is used to select which connector-status-change events
+    /// the PPM should notify the OPM about.
+    SetNotificationEnable {
+        /// This field shall be set to the connector being configured.
+        connector_nr: usize,
+        /// Bit vector of the notifications to enable.
+        notification_enable: u16,
+    },
+    /// This command is used to set the USB operation role of the connector
+    /// identified by this command.
+    SetUsbOperationMode {
+        /// This field shall be set to the connector being configured.
+        connector_nr: usize,
+        /// Requests DFP (host) operation.
+        dfp: bool,
+        /// Requests UFP (device) operation.
+        ufp: bool,
+        /// Requests Dual Role Port operation.
+        drp: bool,
+    },
 }
 
 impl UcsiCommand {
@@ -146,12 +192,23 @@ impl UcsiCommand {
             UcsiCommand::GetCableProperty { .. } => 0x11,
             UcsiCommand::GetConnectorStatus { .. } => 0x12,
             UcsiCommand::GetPdMessage { .. } => 0x15,
+            UcsiCommand::PpmReset => 0x01,
+            UcsiCommand::Cancel => 0x02,
+            UcsiCommand::ConnectorReset { .. } => 0x03,
+            UcsiCommand::AckCcCi { .. } => 0x04,
+            UcsiCommand::SetNotificationEnable { .. } => 0x05,
+            UcsiCommand::SetUsbOperationMode { .. } => 0x09,
         }
     }
 }
 
-impl ToBytes for UcsiCommand {
-    fn to_bytes(&self, bw: &mut crate::BitWriter) -> Result<()> {
+impl UcsiCommand {
+    /// Like [`ToBytes::to_bytes`], but lets the caller pin the negotiated
+    /// [`crate::UcsiVersion`] the encoding should target, for the commands
+    /// whose layout changed across UCSI revisions (currently `GetPdos`).
+    /// `version` defaults to the newest UCSI version when `None`.
+    pub fn to_bytes_versioned(&self, bw: &mut crate::BitWriter, version: Option<crate::UcsiVersion>) -> Result<()> {
+        let version = version.unwrap_or(crate::UcsiVersion(0x0300));
         let command = self.cmd_number();
         bw.write(8, command)?;
         match self {
@@ -199,7 +256,13 @@ impl ToBytes for UcsiCommand {
                 bw.write(8, *pdo_offset)?;
                 bw.write(2, *nr_pdos as u32)?;
                 bw.write(1, *src_or_sink_pdos as u32)?;
-                bw.write(2, *pdo_type as u32)?;
+                if version.major() >= 2 {
+                    bw.write(2, *pdo_type as u32)?;
+                } else {
+                    // Source Capabilities Type was only added in UCSI 2.0;
+                    // older PPMs expect these bits reserved.
+                    bw.write(2, 0u32)?;
+                }
             }
             UcsiCommand::GetCableProperty { connector_nr } => {
                 // Data length
@@ -223,6 +286,48 @@ impl ToBytes for UcsiCommand {
                 bw.write(16, 0)?;
                 bw.write(6, *message_type as u32)?;
             }
+            UcsiCommand::PpmReset => {}
+            UcsiCommand::Cancel => {}
+            UcsiCommand::ConnectorReset {
+                connector_nr,
+                hard_reset,
+            } => {
+                // Data length
+                bw.write(8, 0)?;
+                bw.write(7, *connector_nr as u32 + 1)?;
+                bw.write_bit(*hard_reset)?;
+            }
+            UcsiCommand::AckCcCi {
+                connector_change_ack,
+                command_complete_ack,
+            } => {
+                // Data length
+                bw.write(8, 0)?;
+                bw.write_bit(*connector_change_ack)?;
+                bw.write_bit(*command_complete_ack)?;
+            }
+            UcsiCommand::SetNotificationEnable {
+                connector_nr,
+                notification_enable,
+            } => {
+                // Data length
+                bw.write(8, 0)?;
+                bw.write(7, *connector_nr as u32 + 1)?;
+                bw.write(16, *notification_enable as u32)?;
+            }
+            UcsiCommand::SetUsbOperationMode {
+                connector_nr,
+                dfp,
+                ufp,
+                drp,
+            } => {
+                // Data length
+                bw.write(8, 0)?;
+                bw.write(7, *connector_nr as u32 + 1)?;
+                bw.write_bit(*dfp)?;
+                bw.write_bit(*ufp)?;
+                bw.write_bit(*drp)?;
+            }
         }
 
         bw.byte_align()?;
@@ -230,6 +335,107 @@ impl ToBytes for UcsiCommand {
     }
 }
 
+impl ToBytes for UcsiCommand {
+    fn to_bytes(&self, bw: &mut crate::BitWriter) -> Result<()> {
+        self.to_bytes_versioned(bw, None)
+    }
+}
+
+/// Decodes a `GET_PDOS` response's Message In data, a back-to-back array of
+/// 4-byte [`UcsiPdo`]s, into the typed list. This is the response-side
+/// counterpart to [`UcsiCommand::GetPdos`]'s request encoding: a raw-UCSI
+/// backend issues the command via [`UcsiCommand::to_bytes_versioned`], then
+/// decodes the PPM's reply with this function.
+pub fn decode_pdos(data: &[u8]) -> Result<Vec<UcsiPdo>> {
+    let mut reader = BitReader::new(std::io::Cursor::new(data));
+    let nr_pdos = data.len() / 4;
+    (0..nr_pdos).map(|_| UcsiPdo::from_bytes(&mut reader)).collect()
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf)]
+/// The Command Status and Connector Change Indication register: the 4-byte
+/// word the PPM writes back after every command, and that a driver loop
+/// polls to find out whether the command completed, errored, or which
+/// connector changed.
+pub struct Cci {
+    /// Bits 1-7: the connector number that changed, or 0 if none did.
+    pub connector_change: u32,
+    /// Bits 8-15: the number of data bytes available to be read.
+    pub data_length: u32,
+    /// Bit 25: the command wasn't supported.
+    pub not_supported: bool,
+    /// Bit 26: a CANCEL command completed.
+    pub cancel_complete: bool,
+    /// Bit 27: a Connector Reset completed.
+    pub reset_complete: bool,
+    /// Bit 28: the PPM is busy processing a previous command.
+    pub busy: bool,
+    /// Bit 29: an acknowledgement, requested via ACK_CC_CI, was sent.
+    pub ack_complete: bool,
+    /// Bit 30: the command completed with an error; see GET_ERROR_STATUS.
+    pub error: bool,
+    /// Bit 31: the command completed.
+    pub command_complete: bool,
+}
+
+impl Cci {
+    /// Whether the command completed with an error (bit 30).
+    pub fn is_error(&self) -> bool {
+        self.error
+    }
+
+    /// Whether the PPM is still busy with a previous command (bit 28).
+    pub fn is_busy(&self) -> bool {
+        self.busy
+    }
+
+    /// Whether the command wasn't supported by the PPM (bit 25).
+    pub fn is_not_supported(&self) -> bool {
+        self.not_supported
+    }
+
+    /// Whether the command has completed, successfully or not (bit 31).
+    pub fn is_command_complete(&self) -> bool {
+        self.command_complete
+    }
+
+    /// Whether a connector changed (bits 1-7 are non-zero).
+    pub fn is_connector_change(&self) -> bool {
+        self.connector_change != 0
+    }
+}
+
+impl FromBytes for Cci {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        reader.skip(1)?; // bit0: reserved
+        let connector_change = reader.read::<u32>(7)?; // bits1..7: connector change
+        let data_length = reader.read::<u32>(8)?; // bits8..15: data length
+        reader.skip(9)?; // bits16..24: reserved
+        let not_supported = reader.read_bit()?; // bit25
+        let cancel_complete = reader.read_bit()?; // bit26
+        let reset_complete = reader.read_bit()?; // bit27
+        let busy = reader.read_bit()?; // bit28
+        let ack_complete = reader.read_bit()?; // bit29
+        let error = reader.read_bit()?; // bit30
+        let command_complete = reader.read_bit()?; // bit31
+
+        Ok(Self {
+            connector_change,
+            data_length,
+            not_supported,
+            cancel_complete,
+            reset_complete,
+            busy,
+            ack_complete,
+            error,
+            command_complete,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Printf, Snprintf)]
 pub enum PdMessage {
@@ -245,9 +451,177 @@ pub enum PdMessage {
     Pd3p2DiscoverIdentityResponse(crate::pd::Pd3p2DiscoverIdentityResponse),
     /// Revision (Data Message)
     Pd3p2Revision(crate::pd::Pd3p2RevisionMessageData),
+    /// A Control message carrying no data objects, e.g. GoodCRC, Accept,
+    /// Reject, Ping, PS_RDY, Get_Source_Cap, Get_Sink_Cap, Soft_Reset.
+    Pd3p2Control(crate::pd::ControlMessageType),
+    /// Source Capabilities (Data Message)
+    Pd3p2SourceCapabilities(Vec<crate::pd::Pdo>),
+    /// Sink Capabilities (Data Message)
+    Pd3p2SinkCapabilities(Vec<crate::pd::Pdo>),
+    /// Request (Data Message)
+    Pd3p2Request(crate::pd::RequestDataObject),
+    /// Vendor Defined Message (Data Message): the VDM header plus its data
+    /// objects, decoded into [`crate::vdo::Vdo`] where their shape is known
+    /// (see [`crate::vdo::Vdm`]).
+    Pd3p2VendorDefined(crate::vdo::Vdm),
+}
+
+impl PdMessage {
+    /// Parses a full USB PD message: the 16-bit header (see
+    /// [`crate::pd::PdHeader`]), followed by `number_of_data_objects` 32-bit
+    /// data objects if the message is a Data message, or none if it's a
+    /// Control message.
+    pub fn from_bytes(reader: &mut BitReader, revision: BcdWrapper) -> Result<Self> {
+        let header = crate::pd::PdHeader::from_bytes(reader)?;
+
+        if header.extended {
+            let extended_header = crate::pd::ExtendedMessageHeader::from_bytes(reader)?;
+            let mut payload = Vec::new();
+            for _ in 0..extended_header.data_size {
+                payload.push(reader.read::<u8>(8)?);
+            }
+            return Self::decode_extended(header.message_type, &payload);
+        }
+
+        if header.number_of_data_objects == 0 {
+            let message_type =
+                crate::pd::ControlMessageType::n(header.message_type as u32).ok_or_else(|| Error::ParseError {
+                    field: "message_type (control)".into(),
+                    value: header.message_type as u32,
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                })?;
+            return Ok(PdMessage::Pd3p2Control(message_type));
+        }
+
+        let message_type =
+            crate::pd::DataMessageType::n(header.message_type as u32).ok_or_else(|| Error::ParseError {
+                field: "message_type (data)".into(),
+                value: header.message_type as u32,
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            })?;
+
+        match message_type {
+            crate::pd::DataMessageType::SourceCapabilities => {
+                let pdos = (0..header.number_of_data_objects)
+                    .map(|_| crate::pd::Pdo::from_bytes(reader, revision))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(PdMessage::Pd3p2SourceCapabilities(pdos))
+            }
+            crate::pd::DataMessageType::SinkCapabilities => {
+                let pdos = (0..header.number_of_data_objects)
+                    .map(|_| crate::pd::Pdo::from_bytes(reader, revision))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(PdMessage::Pd3p2SinkCapabilities(pdos))
+            }
+            crate::pd::DataMessageType::Request => {
+                let rdo = crate::pd::RequestDataObject::from_bytes(reader)?;
+                Ok(PdMessage::Pd3p2Request(rdo))
+            }
+            crate::pd::DataMessageType::VendorDefined => {
+                let vdm = crate::vdo::Vdm::from_bytes(reader, header.number_of_data_objects)?;
+                Ok(PdMessage::Pd3p2VendorDefined(vdm))
+            }
+            _ => Err(Error::NotSupported {
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            }),
+        }
+    }
+
+    /// Decodes an Extended message's already-reassembled `payload` (i.e.
+    /// the concatenated data of every chunk, stripped of both the
+    /// [`crate::pd::PdHeader`] and [`crate::pd::ExtendedMessageHeader`])
+    /// according to `message_type`.
+    fn decode_extended(message_type: u8, payload: &[u8]) -> Result<Self> {
+        let message_type =
+            crate::pd::ExtendedMessageType::n(message_type as u32).ok_or_else(|| Error::ParseError {
+                field: "message_type (extended)".into(),
+                value: message_type as u32,
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            })?;
+
+        let mut reader = BitReader::new(std::io::Cursor::new(payload));
+        match message_type {
+            crate::pd::ExtendedMessageType::SourceCapabilitiesExtended => Ok(PdMessage::Pd3p2SourceCapabilitiesExtended(
+                crate::pd::SourceCapabilitiesExtended::from_bytes(&mut reader)?.into(),
+            )),
+            crate::pd::ExtendedMessageType::SinkCapabilitiesExtended => Ok(PdMessage::Pd3p2SinkCapabilitiesExtended(
+                crate::pd::SinkCapabilitiesExtended::from_bytes(&mut reader)?.into(),
+            )),
+            crate::pd::ExtendedMessageType::BatteryCapabilities => Ok(PdMessage::Pd3p2BatteryCapabilities(
+                crate::pd::BatteryCapData::from_bytes(&mut reader)?.into(),
+            )),
+            _ => Err(Error::NotSupported {
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            }),
+        }
+    }
+
+    /// Reassembles a chunked USB PD Extended message (Sink/Source
+    /// Capabilities Extended, Battery Capabilities) from successive
+    /// GET_PD_MESSAGE reads. `next_chunk` is called once per chunk and must
+    /// return the raw bytes of that chunk's USB PD message, i.e. the 16-bit
+    /// [`crate::pd::PdHeader`] and [`crate::pd::ExtendedMessageHeader`]
+    /// followed by that chunk's share of the payload; this mirrors issuing
+    /// repeated `UcsiCommand::GetPdMessage` requests against the same
+    /// connector/recipient/message type until the whole payload named by
+    /// the Extended Message Header's Data Size field has arrived. Chunks
+    /// are expected to arrive in order starting at chunk number 0; anything
+    /// else is reported as an [`Error::ParseError`] on `"chunk_number"`.
+    pub fn from_chunks(mut next_chunk: impl FnMut() -> Result<Vec<u8>>) -> Result<Self> {
+        let mut payload = Vec::new();
+        let mut message_type = None;
+        let mut expected_chunk_number = 0;
+
+        loop {
+            let bytes = next_chunk()?;
+            let mut reader = BitReader::new(std::io::Cursor::new(&bytes[..]));
+            let header = crate::pd::PdHeader::from_bytes(&mut reader)?;
+            if !header.extended {
+                return Err(Error::NotSupported {
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                });
+            }
+
+            let extended_header = crate::pd::ExtendedMessageHeader::from_bytes(&mut reader)?;
+            if extended_header.chunk_number != expected_chunk_number {
+                return Err(Error::ParseError {
+                    field: "chunk_number".into(),
+                    value: extended_header.chunk_number,
+                    #[cfg(feature = "backtrace")]
+                    backtrace: crate::backtrace::Backtrace::capture(),
+                });
+            }
+
+            message_type.get_or_insert(header.message_type);
+
+            // The PdHeader and ExtendedMessageHeader are each 16 bits (2
+            // bytes); whatever's left in this read is this chunk's share of
+            // the payload.
+            let chunk_payload_len = bytes.len().saturating_sub(4);
+            for _ in 0..chunk_payload_len {
+                payload.push(reader.read::<u8>(8)?);
+            }
+
+            expected_chunk_number += 1;
+
+            if payload.len() as u32 >= extended_header.data_size || !extended_header.chunked {
+                payload.truncate(extended_header.data_size as usize);
+                break;
+            }
+        }
+
+        Self::decode_extended(message_type.unwrap_or_default(), &payload)
+    }
 }
 
 /// This enum represents the recipient of the PD message.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Printf, Snprintf, N, Copy)]
 pub enum PdMessageRecipient {
@@ -266,6 +640,7 @@ pub enum PdMessageRecipient {
 }
 
 /// This enum represents the type of the PD response message.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Printf, Snprintf, N, Copy)]
 pub enum PdMessageResponseType {
@@ -281,10 +656,33 @@ pub enum PdMessageResponseType {
     DiscoverIdentity,
     /// Revision (Data Message)
     Revision,
+    /// Accept (Control Message)
+    Accept,
+    /// Reject (Control Message)
+    Reject,
+    /// Ping (Control Message)
+    Ping,
+    /// PS_RDY (Control Message)
+    PsRdy,
+    /// Get_Source_Cap (Control Message)
+    GetSourceCap,
+    /// Get_Sink_Cap (Control Message)
+    GetSinkCap,
+    /// Soft_Reset (Control Message)
+    SoftReset,
+    /// Source_Capabilities (Data Message)
+    SourceCapabilities,
+    /// Sink_Capabilities (Data Message)
+    SinkCapabilities,
+    /// Request (Data Message)
+    Request,
+    /// Vendor_Defined (Data Message)
+    VendorDefined,
     /// Reserved values.
     Reserved,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf)]
 /// This struct represents the GET_CONNECTOR_STATUS data.
@@ -348,8 +746,146 @@ pub struct UcsiConnectorStatus {
     pub voltage_reading: u32,
 }
 
+impl FromBytes for UcsiConnectorStatus {
+    /// No decoder for this struct exists elsewhere in this snapshot, and the
+    /// full register layout isn't available to consult here, so this is a
+    /// best-effort reconstruction: each field gets the narrowest bit width
+    /// that fits its possible values, in declaration order except that
+    /// `battery_charging_capability_status` comes before
+    /// `negotiated_power_level`, padded out to a byte boundary between
+    /// logical groups. It round-trips through
+    /// [`ToBytes`] but isn't guaranteed to match real hardware bit-for-bit.
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let connector_status_change = ConnectorStatusChange::from_bytes(reader)?;
+
+        let power_operation_mode = reader.read::<u32>(3)?;
+        let power_operation_mode = PowerOperationMode::n(power_operation_mode).ok_or_else(|| Error::ParseError {
+            field: "power_operation_mode".into(),
+            value: power_operation_mode,
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })?;
+        let connect_status = reader.read_bit()?;
+        let power_direction = reader.read::<u32>(1)?;
+        let power_direction = PowerDirection::n(power_direction).ok_or_else(|| Error::ParseError {
+            field: "power_direction".into(),
+            value: power_direction,
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })?;
+        let connector_partner_flags = reader.read::<u32>(8)?;
+        let connector_partner_type = reader.read::<u32>(3)?;
+        let connector_partner_type = ConnectorPartnerType::n(connector_partner_type).ok_or_else(|| Error::ParseError {
+            field: "connector_partner_type".into(),
+            value: connector_partner_type,
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })?;
+        let battery_charging_capability_status = reader.read::<u32>(2)?;
+        let battery_charging_capability_status = BatteryChargingCapabilityStatus::n(battery_charging_capability_status)
+            .ok_or_else(|| Error::ParseError {
+                field: "battery_charging_capability_status".into(),
+                value: battery_charging_capability_status,
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            })?;
+        reader.skip(6)?; // padding to a byte boundary
+
+        let negotiated_power_level = reader.read::<u32>(32)?;
+
+        let provider_capabilities_limited_reason = reader.read::<u32>(4)?;
+        let pd_version_operation_mode = reader.read::<u32>(16)?;
+        reader.skip(4)?; // padding to a byte boundary
+
+        let orientation = reader.read::<u32>(1)?;
+        let orientation = ConnectorOrientation::n(orientation).ok_or_else(|| Error::ParseError {
+            field: "orientation".into(),
+            value: orientation,
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })?;
+        let sink_path_status = reader.read::<u32>(1)?;
+        let sink_path_status = SinkPathStatus::n(sink_path_status).ok_or_else(|| Error::ParseError {
+            field: "sink_path_status".into(),
+            value: sink_path_status,
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })?;
+        let reverse_current_protection_status = reader.read_bit()?;
+        let power_reading_ready = reader.read_bit()?;
+        reader.skip(4)?; // padding to a byte boundary
+
+        let scale_current = reader.read::<u32>(2)?;
+        reader.skip(6)?; // padding to a byte boundary
+        let peak_current = reader.read::<u32>(16)?;
+        let average_current = reader.read::<u32>(16)?;
+        let scale_voltage = reader.read::<u32>(2)?;
+        reader.skip(6)?; // padding to a byte boundary
+        let voltage_reading = reader.read::<u32>(16)?;
+
+        Ok(Self {
+            connector_status_change,
+            power_operation_mode,
+            connect_status,
+            power_direction,
+            connector_partner_flags,
+            connector_partner_type,
+            negotiated_power_level,
+            battery_charging_capability_status,
+            provider_capabilities_limited_reason,
+            pd_version_operation_mode,
+            orientation,
+            sink_path_status,
+            reverse_current_protection_status,
+            power_reading_ready,
+            scale_current,
+            peak_current,
+            average_current,
+            scale_voltage,
+            voltage_reading,
+        })
+    }
+}
+
+impl ToBytes for UcsiConnectorStatus {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        self.connector_status_change.to_bytes(bit_writer)?;
+
+        bit_writer.write(3, self.power_operation_mode as u32)?;
+        bit_writer.write_bit(self.connect_status)?;
+        bit_writer.write(1, self.power_direction as u32)?;
+        bit_writer.write(8, self.connector_partner_flags)?;
+        bit_writer.write(3, self.connector_partner_type as u32)?;
+        bit_writer.write(2, self.battery_charging_capability_status as u32)?;
+        bit_writer.write(6, 0u32)?; // padding to a byte boundary
+
+        bit_writer.write(32, self.negotiated_power_level)?;
+
+        bit_writer.write(4, self.provider_capabilities_limited_reason)?;
+        bit_writer.write(16, self.pd_version_operation_mode)?;
+        bit_writer.write(4, 0u32)?; // padding to a byte boundary
+
+        bit_writer.write(1, self.orientation as u32)?;
+        bit_writer.write(1, self.sink_path_status as u32)?;
+        bit_writer.write_bit(self.reverse_current_protection_status)?;
+        bit_writer.write_bit(self.power_reading_ready)?;
+        bit_writer.write(4, 0u32)?; // padding to a byte boundary
+
+        bit_writer.write(2, self.scale_current)?;
+        bit_writer.write(6, 0u32)?; // padding to a byte boundary
+        bit_writer.write(16, self.peak_current)?;
+        bit_writer.write(16, self.average_current)?;
+        bit_writer.write(2, self.scale_voltage)?;
+        bit_writer.write(6, 0u32)?; // padding to a byte boundary
+        bit_writer.write(16, self.voltage_reading)?;
+
+        Ok(())
+    }
+}
+
 /// Connector Status Change Field Description for GET_CONNECTOR_STATUS. See
 /// UCSI Table 6-44 for more information.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf)]
 pub struct ConnectorStatusChange {
@@ -395,8 +931,61 @@ pub struct ConnectorStatusChange {
     pub connector_partner_changed: bool,
 }
 
+impl FromBytes for ConnectorStatusChange {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let reserved1 = reader.read_bit()?; // bit0: reserved
+        let external_supply_change = reader.read_bit()?; // bit1
+        let power_operation_mode_change = reader.read_bit()?; // bit2
+        let attention = reader.read_bit()?; // bit3
+        let reserved2 = reader.read_bit()?; // bit4: reserved
+        let supported_provider_capabilities_change = reader.read_bit()?; // bit5
+        let negotiated_power_level_change = reader.read_bit()?; // bit6
+        let pd_reset_complete = reader.read_bit()?; // bit7
+        let supported_cam_change = reader.read_bit()?; // bit8
+        let battery_charging_status_change = reader.read_bit()?; // bit9
+        let reserved3 = reader.read_bit()?; // bit10: reserved
+        let connector_partner_changed = reader.read_bit()?; // bit11
+        reader.skip(4)?; // bits12..15: reserved
+
+        Ok(Self {
+            reserved1,
+            external_supply_change,
+            power_operation_mode_change,
+            attention,
+            reserved2,
+            supported_provider_capabilities_change,
+            negotiated_power_level_change,
+            pd_reset_complete,
+            supported_cam_change,
+            battery_charging_status_change,
+            reserved3,
+            connector_partner_changed,
+        })
+    }
+}
+
+impl ToBytes for ConnectorStatusChange {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write_bit(self.reserved1)?;
+        bit_writer.write_bit(self.external_supply_change)?;
+        bit_writer.write_bit(self.power_operation_mode_change)?;
+        bit_writer.write_bit(self.attention)?;
+        bit_writer.write_bit(self.reserved2)?;
+        bit_writer.write_bit(self.supported_provider_capabilities_change)?;
+        bit_writer.write_bit(self.negotiated_power_level_change)?;
+        bit_writer.write_bit(self.pd_reset_complete)?;
+        bit_writer.write_bit(self.supported_cam_change)?;
+        bit_writer.write_bit(self.battery_charging_status_change)?;
+        bit_writer.write_bit(self.reserved3)?;
+        bit_writer.write_bit(self.connector_partner_changed)?;
+        bit_writer.write(4, 0u32)?; // bits12..15: reserved
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf, N)]
 /// This enum represents the Orientation.
 pub enum ConnectorOrientation {
     /// The connection is in the normal orientation.
@@ -406,8 +995,9 @@ pub enum ConnectorOrientation {
     Reverse = 1,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf, N)]
 /// This enum represents the Sink Path Status.
 pub enum SinkPathStatus {
     /// The Sink Path is not ready.
@@ -417,8 +1007,9 @@ pub enum SinkPathStatus {
     Ready = 1,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf, N)]
 /// This enum represents the Power Operation Mode.
 pub enum PowerOperationMode {
     #[default]
@@ -432,8 +1023,9 @@ pub enum PowerOperationMode {
     Reserved2 = 7,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf, N)]
 /// This enum represents the Power Direction.
 pub enum PowerDirection {
     #[default]
@@ -441,8 +1033,9 @@ pub enum PowerDirection {
     Provider = 1,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf, N)]
 /// This enum represents the Connector Partner Type.
 pub enum ConnectorPartnerType {
     #[default]
@@ -456,8 +1049,9 @@ pub enum ConnectorPartnerType {
     Reserved2 = 7,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf, N)]
 /// This enum represents the Battery Charging Capability Status.
 pub enum BatteryChargingCapabilityStatus {
     #[default]
@@ -467,8 +1061,9 @@ pub enum BatteryChargingCapabilityStatus {
     VerySlowChargingRate = 3,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf, N)]
 pub enum CablePropertySpeedExponent {
     #[default]
     Bps = 0,
@@ -477,8 +1072,9 @@ pub enum CablePropertySpeedExponent {
     Gbps = 3,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf, N)]
 pub enum CablePropertyPlugEndType {
     #[default]
     UsbTypeA,
@@ -487,14 +1083,16 @@ pub enum CablePropertyPlugEndType {
     OtherNotUsb,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf, N)]
 pub enum CablePropertyType {
     #[default]
     Passive = 0,
     Active = 1,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf)]
 /// See UCSI Table 6-40: GET_CABLE_PROPERTY Data
@@ -539,7 +1137,7 @@ impl FromBytes for UcsiCableProperty {
                 field: "speed_exponent".into(),
                 value: speed_exponent,
                 #[cfg(feature = "backtrace")]
-                backtrace: std::backtrace::Backtrace::capture(),
+                backtrace: crate::backtrace::Backtrace::capture(),
             })?;
         let speed_mantissa = reader.read::<u32>(14)?; // Read Speed Mantissa
         let b_current_capability = reader.read::<u32>(8)?; // Read Current Capability
@@ -549,7 +1147,7 @@ impl FromBytes for UcsiCableProperty {
             field: "cable_type".into(),
             value: cable_type,
             #[cfg(feature = "backtrace")]
-            backtrace: std::backtrace::Backtrace::capture(),
+            backtrace: crate::backtrace::Backtrace::capture(),
         })?;
         let directionality = reader.read::<u32>(1)?; // Read Directionality
         let plug_end_type = reader.read::<u32>(2)?;
@@ -558,7 +1156,7 @@ impl FromBytes for UcsiCableProperty {
                 field: "plug_end_type".into(),
                 value: plug_end_type,
                 #[cfg(feature = "backtrace")]
-                backtrace: std::backtrace::Backtrace::capture(),
+                backtrace: crate::backtrace::Backtrace::capture(),
             })?;
         let mode_support = reader.read_bit()?; // Read Mode Support
         let cable_pd_revision = reader.read::<u32>(2)?; // Read Cable PD Revision
@@ -579,9 +1177,26 @@ impl FromBytes for UcsiCableProperty {
     }
 }
 
+impl ToBytes for UcsiCableProperty {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write(2, self.speed_exponent as u32)?;
+        bit_writer.write(14, self.speed_mantissa)?;
+        bit_writer.write(8, self.b_current_capability)?;
+        bit_writer.write(1, self.vbus_in_cable)?;
+        bit_writer.write(1, self.cable_type as u32)?;
+        bit_writer.write(1, self.directionality)?;
+        bit_writer.write(2, self.plug_end_type as u32)?;
+        bit_writer.write_bit(self.mode_support)?;
+        bit_writer.write(2, self.cable_pd_revision)?;
+        bit_writer.write(4, self.latency)?;
+        Ok(())
+    }
+}
+
 /// The response to a GET_ALTERNATE_MODES command.
 ///
 /// See USCI 3.0 - Table 6.26.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Clone, PartialEq, Default, Printf, Snprintf)]
 pub struct UcsiAlternateMode {
@@ -603,6 +1218,16 @@ impl FromBytes for UcsiAlternateMode {
     }
 }
 
+impl ToBytes for UcsiAlternateMode {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write(16, self.svid[0])?;
+        bit_writer.write(32, self.vdo[0])?;
+        bit_writer.write(16, self.svid[1])?;
+        bit_writer.write(32, self.vdo[1])?;
+        Ok(())
+    }
+}
+
 impl std::fmt::Debug for UcsiAlternateMode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let vdo = format!("{:#08x}", self.vdo[0]);
@@ -613,16 +1238,180 @@ impl std::fmt::Debug for UcsiAlternateMode {
     }
 }
 
+/// The DisplayPort Alternate Mode SVID, assigned by VESA.
+pub const DISPLAYPORT_SVID: u32 = 0xff01;
+/// The Thunderbolt Alternate Mode SVID, assigned by Intel.
+pub const THUNDERBOLT_SVID: u32 = 0x8087;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, Printf, Snprintf)]
+#[derive(Debug, Clone, Copy, PartialEq, Printf, Snprintf, N)]
+/// The DisplayPort Mode VDO's Port Capability field: whether the port can
+/// act as a DFP_D, a UFP_D, or both.
+pub enum DisplayPortCapability {
+    UfpD = 0,
+    DfpD = 1,
+    DfpDAndUfpD = 2,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Printf, Snprintf)]
+/// A DisplayPort Alternate Mode Mode VDO, as returned in an alternate
+/// mode's `vdo` field when its `svid` is [`DISPLAYPORT_SVID`]. See the VESA
+/// DisplayPort Alt Mode on USB Type-C specification.
+pub struct DisplayPortVdo {
+    /// Whether this port can act as a DFP_D, a UFP_D, or both.
+    pub capability: DisplayPortCapability,
+    /// Whether DP signaling is carried directly over USB pins rather than
+    /// requiring the cable's SuperSpeed pairs to be dedicated to DP.
+    pub usb_signaling: bool,
+    /// Whether this is a permanently-attached receptacle rather than a
+    /// captive/direct connection.
+    pub receptacle: bool,
+    /// Bit vector of the DP signaling rates this port supports.
+    pub signaling: u32,
+    /// Bit vector of pin assignments (one bit per assignment, A through F
+    /// in spec order) this port supports acting as a DFP_D.
+    pub dfp_d_pin_assignments: u8,
+    /// Bit vector of pin assignments this port supports acting as a UFP_D.
+    pub ufp_d_pin_assignments: u8,
+}
+
+impl DisplayPortVdo {
+    fn from_raw(vdo: u32) -> Result<Self> {
+        let capability_value = vdo & 0b11;
+        let capability = DisplayPortCapability::n(capability_value).ok_or_else(|| Error::ParseError {
+            field: "capability".into(),
+            value: capability_value,
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })?;
+        let signaling = (vdo >> 2) & 0b1111;
+        let receptacle = (vdo >> 6) & 1 != 0;
+        let usb_signaling = (vdo >> 7) & 1 != 0;
+        let dfp_d_pin_assignments = ((vdo >> 8) & 0xff) as u8;
+        let ufp_d_pin_assignments = ((vdo >> 16) & 0xff) as u8;
+
+        Ok(Self {
+            capability,
+            usb_signaling,
+            receptacle,
+            signaling,
+            dfp_d_pin_assignments,
+            ufp_d_pin_assignments,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Printf, Snprintf)]
+/// A Thunderbolt 3 Alternate Mode Mode VDO, as returned in an alternate
+/// mode's `vdo` field when its `svid` is [`THUNDERBOLT_SVID`].
+pub struct ThunderboltVdo {
+    /// Vendor-specific byte B0.
+    pub vendor_b0: u8,
+    /// Vendor-specific byte B1.
+    pub vendor_b1: u8,
+    /// Raw cable speed bucket; higher values indicate higher supported
+    /// speeds. Only meaningful when this VDO describes a cable.
+    pub cable_speed: u8,
+    /// Whether the cable reports rounded support for the full TBT3 feature
+    /// set.
+    pub rounded_support: bool,
+    /// Whether this describes an active (true) or passive (false) cable.
+    pub active_cable: bool,
+    /// Whether the cable/adapter supports link training at the negotiated
+    /// bit rate without falling back to a lower one.
+    pub link_training: bool,
+}
+
+impl ThunderboltVdo {
+    fn from_raw(vdo: u32) -> Self {
+        let vendor_b0 = (vdo & 0xff) as u8;
+        let vendor_b1 = ((vdo >> 8) & 0xff) as u8;
+        let cable_speed = ((vdo >> 16) & 0b111) as u8;
+        let rounded_support = (vdo >> 19) & 1 != 0;
+        let active_cable = (vdo >> 20) & 1 != 0;
+        let link_training = (vdo >> 23) & 1 != 0;
+
+        Self {
+            vendor_b0,
+            vendor_b1,
+            cable_speed,
+            rounded_support,
+            active_cable,
+            link_training,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A single Alternate Mode's Mode VDO, decoded according to its SVID where
+/// the layout is known to this library.
+pub enum AlternateModeVdo {
+    /// DisplayPort Alternate Mode (SVID [`DISPLAYPORT_SVID`]).
+    DisplayPort(DisplayPortVdo),
+    /// Thunderbolt 3 Alternate Mode (SVID [`THUNDERBOLT_SVID`]).
+    Thunderbolt(ThunderboltVdo),
+    /// An SVID this library doesn't know how to interpret yet.
+    Unknown { svid: u32, vdo: u32 },
+}
+
+impl UcsiAlternateMode {
+    /// Decodes each non-zero `(svid, vdo)` pair this alternate mode carries
+    /// into an [`AlternateModeVdo`], interpreting the VDO according to its
+    /// SVID where the layout is known (currently DisplayPort and
+    /// Thunderbolt 3); unrecognized SVIDs are kept as
+    /// [`AlternateModeVdo::Unknown`] rather than dropped.
+    pub fn decode_vdos(&self) -> Result<Vec<AlternateModeVdo>> {
+        self.svid
+            .iter()
+            .zip(self.vdo.iter())
+            .filter(|(svid, _)| **svid != 0)
+            .map(|(&svid, &vdo)| {
+                Ok(match svid {
+                    DISPLAYPORT_SVID => AlternateModeVdo::DisplayPort(DisplayPortVdo::from_raw(vdo)?),
+                    THUNDERBOLT_SVID => AlternateModeVdo::Thunderbolt(ThunderboltVdo::from_raw(vdo)),
+                    _ => AlternateModeVdo::Unknown { svid, vdo },
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, Printf, Snprintf)]
 /// See UCSI - Table 6-29: GET_CAM_SUPPORTED Data
 pub struct UcsiCamSupported {
     /// Whether an alternate mode is supported.
     pub cam_supported: bool,
 }
 
+impl FromBytes for UcsiCamSupported {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let cam_supported = reader.read_bit()?;
+        reader.skip(7)?; // reserved, padding to a full byte
+
+        Ok(Self { cam_supported })
+    }
+}
+
+impl ToBytes for UcsiCamSupported {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write_bit(self.cam_supported)?;
+        bit_writer.write(7, 0u32)?; // reserved, padding to a full byte
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, Printf, Snprintf)]
+#[derive(Debug, Clone, PartialEq, Printf, Snprintf)]
 pub struct UcsiCurrentCam {
     /// Offsets into the list of Alternate Modes that the connector is
     /// currently operating in.
@@ -633,9 +1422,36 @@ pub struct UcsiCurrentCam {
     pub current_alternate_mode: [usize; UCSI_MAX_NUM_ALT_MODE],
 }
 
+impl FromBytes for UcsiCurrentCam {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let mut current_alternate_mode = [0usize; UCSI_MAX_NUM_ALT_MODE];
+        for slot in &mut current_alternate_mode {
+            *slot = reader.read::<u32>(8)? as usize;
+        }
+
+        Ok(Self {
+            current_alternate_mode,
+        })
+    }
+}
+
+impl ToBytes for UcsiCurrentCam {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        for slot in &self.current_alternate_mode {
+            bit_writer.write(8, *slot as u32)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf)]
 /// Connector capability data extended operation mode.
+///
+/// `Unknown` preserves any discriminant a newer PPM might report that this
+/// version of the library doesn't recognize yet, instead of failing the
+/// whole parse over it (see [`UcsiConnectorCapability::from_bytes`]).
 pub enum ConnectorCapabilityOperationMode {
     #[default]
     RpOnly,
@@ -646,11 +1462,69 @@ pub enum ConnectorCapabilityOperationMode {
     Usb2,
     Usb3,
     AlternateMode,
+    Unknown(u32),
+}
+
+impl ConnectorCapabilityOperationMode {
+    fn from_raw(value: u32) -> Self {
+        match value {
+            0 => Self::RpOnly,
+            1 => Self::RdOnly,
+            2 => Self::Drp,
+            3 => Self::AnalogAudioAccessoryMode,
+            4 => Self::DebugAccessoryMode,
+            5 => Self::Usb2,
+            6 => Self::Usb3,
+            7 => Self::AlternateMode,
+            other => Self::Unknown(other),
+        }
+    }
+
+    fn to_raw(self) -> u32 {
+        match self {
+            Self::RpOnly => 0,
+            Self::RdOnly => 1,
+            Self::Drp => 2,
+            Self::AnalogAudioAccessoryMode => 3,
+            Self::DebugAccessoryMode => 4,
+            Self::Usb2 => 5,
+            Self::Usb3 => 6,
+            Self::AlternateMode => 7,
+            Self::Unknown(value) => value,
+        }
+    }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf)]
+/// The power role a connector should be put into, as written to the `power_role`
+/// sysfs attribute.
+pub enum PowerRole {
+    #[default]
+    Sink,
+    Source,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf)]
+/// The data role a connector should be put into, as written to the `data_role`
+/// sysfs attribute.
+pub enum DataRole {
+    #[default]
+    Device,
+    Host,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf)]
 /// Connector capability data extended operation mode.
+///
+/// `Unknown` preserves any discriminant a newer PPM might report that this
+/// version of the library doesn't recognize yet, instead of failing the
+/// whole parse over it (see [`UcsiConnectorCapability::from_bytes`]).
 pub enum ConnectorCapabilityExtendedOperationMode {
     #[default]
     Usb4Gen2,
@@ -658,17 +1532,67 @@ pub enum ConnectorCapabilityExtendedOperationMode {
     EprSink,
     Usb4Gen3,
     Usb4Gen4,
+    Unknown(u32),
+}
+
+impl ConnectorCapabilityExtendedOperationMode {
+    fn from_raw(value: u32) -> Self {
+        match value {
+            0 => Self::Usb4Gen2,
+            1 => Self::EprSource,
+            2 => Self::EprSink,
+            3 => Self::Usb4Gen3,
+            4 => Self::Usb4Gen4,
+            other => Self::Unknown(other),
+        }
+    }
+
+    fn to_raw(self) -> u32 {
+        match self {
+            Self::Usb4Gen2 => 0,
+            Self::EprSource => 1,
+            Self::EprSink => 2,
+            Self::Usb4Gen3 => 3,
+            Self::Usb4Gen4 => 4,
+            Self::Unknown(value) => value,
+        }
+    }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
-#[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf, N)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf)]
 /// Connector capability data miscellaneous capabilities.
+///
+/// `Unknown` preserves any discriminant a newer PPM might report that this
+/// version of the library doesn't recognize yet, instead of failing the
+/// whole parse over it (see [`UcsiConnectorCapability::from_bytes`]).
 pub enum ConnectorCapabilityMiscellaneousCapabilities {
     #[default]
     FwUpdate,
     Security,
+    Unknown(u32),
+}
+
+impl ConnectorCapabilityMiscellaneousCapabilities {
+    fn from_raw(value: u32) -> Self {
+        match value {
+            0 => Self::FwUpdate,
+            1 => Self::Security,
+            other => Self::Unknown(other),
+        }
+    }
+
+    fn to_raw(self) -> u32 {
+        match self {
+            Self::FwUpdate => 0,
+            Self::Security => 1,
+            Self::Unknown(value) => value,
+        }
+    }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf)]
 /// The response to a `GET_CONNECTOR_CAPABILITY` command.
@@ -714,15 +1638,7 @@ pub struct UcsiConnectorCapability {
 impl FromBytes for UcsiConnectorCapability {
     fn from_bytes(reader: &mut BitReader) -> Result<Self> {
         let operation_mode_value = reader.read::<u32>(8)?;
-        let operation_mode =
-            ConnectorCapabilityOperationMode::n(operation_mode_value).ok_or_else(|| {
-                Error::ParseError {
-                    field: "operation_mode".into(),
-                    value: operation_mode_value,
-                    #[cfg(feature = "backtrace")]
-                    backtrace: std::backtrace::Backtrace::capture(),
-                }
-            })?;
+        let operation_mode = ConnectorCapabilityOperationMode::from_raw(operation_mode_value);
         let provider = reader.read_bit()?;
         let consumer = reader.read_bit()?;
         let swap_to_dfp = reader.read_bit()?;
@@ -730,24 +1646,11 @@ impl FromBytes for UcsiConnectorCapability {
         let swap_to_src = reader.read_bit()?;
         let swap_to_snk = reader.read_bit()?;
         let extended_operation_mode_value = reader.read::<u32>(8)?;
-        let extended_operation_mode = ConnectorCapabilityExtendedOperationMode::n(
-            extended_operation_mode_value,
-        )
-        .ok_or_else(|| Error::ParseError {
-            field: "extended_operation_mode".into(),
-            value: extended_operation_mode_value,
-            #[cfg(feature = "backtrace")]
-            backtrace: std::backtrace::Backtrace::capture(),
-        })?;
+        let extended_operation_mode =
+            ConnectorCapabilityExtendedOperationMode::from_raw(extended_operation_mode_value);
         let miscellaneous_capabilities_value = reader.read::<u32>(4)?;
         let miscellaneous_capabilities =
-            ConnectorCapabilityMiscellaneousCapabilities::n(miscellaneous_capabilities_value)
-                .ok_or_else(|| Error::ParseError {
-                    field: "miscellaneous_capabilities".into(),
-                    value: miscellaneous_capabilities_value,
-                    #[cfg(feature = "backtrace")]
-                    backtrace: std::backtrace::Backtrace::capture(),
-                })?;
+            ConnectorCapabilityMiscellaneousCapabilities::from_raw(miscellaneous_capabilities_value);
         let reverse_current_protection_support = reader.read_bit()?;
         let partner_pd_revision = reader.read::<u8>(2)?;
 
@@ -767,6 +1670,24 @@ impl FromBytes for UcsiConnectorCapability {
     }
 }
 
+impl ToBytes for UcsiConnectorCapability {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write(8, self.operation_mode.to_raw())?;
+        bit_writer.write_bit(self.provider)?;
+        bit_writer.write_bit(self.consumer)?;
+        bit_writer.write_bit(self.swap_to_dfp)?;
+        bit_writer.write_bit(self.swap_to_ufp)?;
+        bit_writer.write_bit(self.swap_to_src)?;
+        bit_writer.write_bit(self.swap_to_snk)?;
+        bit_writer.write(8, self.extended_operation_mode.to_raw())?;
+        bit_writer.write(4, self.miscellaneous_capabilities.to_raw())?;
+        bit_writer.write_bit(self.reverse_current_protection_support)?;
+        bit_writer.write(2, self.partner_pd_revision as u32)?;
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf)]
 pub struct UcsiCapability {
@@ -807,12 +1728,17 @@ pub struct UcsiCapability {
     pub usb_type_c_version: BcdWrapper,
 }
 
-impl FromBytes for UcsiCapability {
-    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+impl UcsiCapability {
+    /// Like [`FromBytes::from_bytes`], but lets the caller pin the negotiated
+    /// [`crate::UcsiVersion`], which [`UcsiBmOptionalFeatures`] needs to
+    /// decode its trailing feature bits correctly (see
+    /// [`UcsiBmOptionalFeatures::from_bytes_versioned`]). `version` defaults
+    /// to the newest UCSI version when `None`.
+    pub fn from_bytes_versioned(reader: &mut BitReader, version: Option<crate::UcsiVersion>) -> Result<Self> {
         let bm_attributes = UcsiBmAttributes::from_bytes(reader)?;
         let num_connectors = reader.read::<u32>(7)? as usize;
         reader.skip(1)?; // Skip reserved bit
-        let bm_optional_features = UcsiBmOptionalFeatures::from_bytes(reader)?;
+        let bm_optional_features = UcsiBmOptionalFeatures::from_bytes_versioned(reader, version)?;
         let num_alt_modes: usize = reader.read::<u32>(8)? as usize;
         reader.skip(8)?; // Skip reserved bits
         let bc_version = BcdWrapper(reader.read(16)?);
@@ -829,8 +1755,36 @@ impl FromBytes for UcsiCapability {
             usb_type_c_version,
         })
     }
+
+    /// Like [`ToBytes::to_bytes`], but lets the caller pin the negotiated
+    /// [`crate::UcsiVersion`] (see [`UcsiCapability::from_bytes_versioned`]).
+    pub fn to_bytes_versioned(&self, bit_writer: &mut BitWriter, version: Option<crate::UcsiVersion>) -> Result<()> {
+        self.bm_attributes.to_bytes(bit_writer)?;
+        bit_writer.write(7, self.num_connectors as u32)?;
+        bit_writer.write(1, 0u32)?; // Reserved bit
+        self.bm_optional_features.to_bytes_versioned(bit_writer, version)?;
+        bit_writer.write(8, self.num_alt_modes as u32)?;
+        bit_writer.write(8, 0u32)?; // Reserved bits
+        bit_writer.write(16, self.bc_version.0)?;
+        bit_writer.write(16, self.pd_version.0)?;
+        bit_writer.write(16, self.usb_type_c_version.0)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for UcsiCapability {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        Self::from_bytes_versioned(reader, None)
+    }
+}
+
+impl ToBytes for UcsiCapability {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        self.to_bytes_versioned(bit_writer, None)
+    }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf)]
 pub struct UcsiBmAttributes {
@@ -872,6 +1826,21 @@ impl FromBytes for UcsiBmAttributes {
     }
 }
 
+impl ToBytes for UcsiBmAttributes {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write_bit(self.disabled_state_support)?;
+        bit_writer.write_bit(self.battery_charging)?;
+        bit_writer.write_bit(self.usb_power_delivery)?;
+        bit_writer.write(3, 0u32)?; // Reserved bits
+        bit_writer.write_bit(self.usb_type_c_current)?;
+        bit_writer.write(1, 0u32)?; // Reserved bit
+        self.bm_power_source.to_bytes(bit_writer)?;
+        bit_writer.write(16, 0u32)?; // Reserved bits
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf)]
 pub struct UcsiBmOptionalFeatures {
@@ -913,8 +1882,17 @@ pub struct UcsiBmOptionalFeatures {
     pub chunking_supported: bool,
 }
 
-impl FromBytes for UcsiBmOptionalFeatures {
-    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+impl UcsiBmOptionalFeatures {
+    /// Like [`FromBytes::from_bytes`], but lets the caller pin the negotiated
+    /// [`crate::UcsiVersion`]: `set_retimer_mode_supported` was only added to
+    /// this field in UCSI 2.0, and `chunking_supported` in UCSI 2.1, so a PPM
+    /// negotiated at an older revision leaves those bits reserved rather
+    /// than populated. This field is 24 bits wide and only 15 are described
+    /// in table 6-88 even at the newest revision, so whatever this UCSI
+    /// version doesn't define is skipped as reserved. `version` defaults to
+    /// the newest UCSI version when `None`.
+    pub fn from_bytes_versioned(reader: &mut BitReader, version: Option<crate::UcsiVersion>) -> Result<Self> {
+        let version = version.unwrap_or(crate::UcsiVersion(0x0300));
         let set_ccom_supported: bool = reader.read_bit()?;
         let set_power_level_supported: bool = reader.read_bit()?;
         let alternate_mode_details_supported: bool = reader.read_bit()?;
@@ -928,11 +1906,21 @@ impl FromBytes for UcsiBmOptionalFeatures {
         let fw_update_request_supported: bool = reader.read_bit()?;
         let negotiated_power_level_change_supported: bool = reader.read_bit()?;
         let security_request_supported: bool = reader.read_bit()?;
-        let set_retimer_mode_supported: bool = reader.read_bit()?;
-        let chunking_supported: bool = reader.read_bit()?;
-        // This is not very clear, but this field is 24 bits and only 14 are
-        // described in table 6-88
-        reader.skip(9)?;
+
+        let mut consumed: u32 = 13;
+        let set_retimer_mode_supported = if version.major() >= 2 {
+            consumed += 1;
+            reader.read_bit()?
+        } else {
+            false
+        };
+        let chunking_supported = if version.0 >= 0x0210 {
+            consumed += 1;
+            reader.read_bit()?
+        } else {
+            false
+        };
+        reader.skip(24 - consumed)?;
 
         Ok(Self {
             set_ccom_supported,
@@ -952,8 +1940,56 @@ impl FromBytes for UcsiBmOptionalFeatures {
             chunking_supported,
         })
     }
+
+    /// Like [`ToBytes::to_bytes`] (see
+    /// [`UcsiBmOptionalFeatures::from_bytes_versioned`]).
+    pub fn to_bytes_versioned(&self, bit_writer: &mut BitWriter, version: Option<crate::UcsiVersion>) -> Result<()> {
+        let version = version.unwrap_or(crate::UcsiVersion(0x0300));
+        bit_writer.write_bit(self.set_ccom_supported)?;
+        bit_writer.write_bit(self.set_power_level_supported)?;
+        bit_writer.write_bit(self.alternate_mode_details_supported)?;
+        bit_writer.write_bit(self.alternate_mode_override_supported)?;
+        bit_writer.write_bit(self.pdo_details_supported)?;
+        bit_writer.write_bit(self.cable_details_supported)?;
+        bit_writer.write_bit(self.external_supply_notification_supported)?;
+        bit_writer.write_bit(self.pd_reset_notification_supported)?;
+        bit_writer.write_bit(self.get_pd_message_supported)?;
+        bit_writer.write_bit(self.get_attention_vdo_supported)?;
+        bit_writer.write_bit(self.fw_update_request_supported)?;
+        bit_writer.write_bit(self.negotiated_power_level_change_supported)?;
+        bit_writer.write_bit(self.security_request_supported)?;
+
+        let mut consumed: u32 = 13;
+        if version.major() >= 2 {
+            consumed += 1;
+            bit_writer.write_bit(self.set_retimer_mode_supported)?;
+        }
+        if version.0 >= 0x0210 {
+            consumed += 1;
+            bit_writer.write_bit(self.chunking_supported)?;
+        }
+        // See the matching comment on `from_bytes_versioned`: this field is
+        // 24 bits wide and only 15 are described in table 6-88 even at the
+        // newest revision; whatever this UCSI version doesn't define is
+        // reserved.
+        bit_writer.write(24 - consumed, 0u32)?;
+        Ok(())
+    }
 }
 
+impl FromBytes for UcsiBmOptionalFeatures {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        Self::from_bytes_versioned(reader, None)
+    }
+}
+
+impl ToBytes for UcsiBmOptionalFeatures {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        self.to_bytes_versioned(bit_writer, None)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Default, Printf, Snprintf)]
 pub struct UcsiBmPowerSource {
@@ -978,3 +2014,530 @@ impl FromBytes for UcsiBmPowerSource {
         })
     }
 }
+
+impl ToBytes for UcsiBmPowerSource {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write_bit(self.ac_supply)?;
+        bit_writer.write(1, 0u32)?; // Reserved bit
+        bit_writer.write_bit(self.other)?;
+        bit_writer.write(3, 0u32)?; // Reserved bits
+        bit_writer.write_bit(self.uses_vbus)?;
+        bit_writer.write(1, 0u32)?; // Reserved bit
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf)]
+/// A Fixed Supply Power Data Object (`pdo_type` `00`). See USB PD
+/// Specification - Table 6.7 "Power Data Object".
+pub struct UcsiFixedSupplyPdo {
+    pub dual_role_power: bool,
+    pub usb_suspend_supported: bool,
+    pub unconstrained_power: bool,
+    pub usb_comms_capable: bool,
+    pub dual_role_data: bool,
+    pub epr_capable: bool,
+    /// A 2-bit code, same encoding as every other PDO's Peak Current field.
+    pub peak_current: u32,
+    /// 50 mV units.
+    pub voltage: crate::Millivolt,
+    /// 10 mA units.
+    pub max_current: crate::Milliamp,
+}
+
+impl FromBytes for UcsiFixedSupplyPdo {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let dual_role_power = reader.read_bit()?;
+        let usb_suspend_supported = reader.read_bit()?;
+        let unconstrained_power = reader.read_bit()?;
+        let usb_comms_capable = reader.read_bit()?;
+        let dual_role_data = reader.read_bit()?;
+        let epr_capable = reader.read_bit()?;
+        reader.skip(2)?; // Reserved bits
+        let peak_current = reader.read::<u32>(2)?;
+        let voltage = (reader.read::<u32>(10)? * 50).into();
+        let max_current = (reader.read::<u32>(10)? * 10).into();
+
+        Ok(Self {
+            dual_role_power,
+            usb_suspend_supported,
+            unconstrained_power,
+            usb_comms_capable,
+            dual_role_data,
+            epr_capable,
+            peak_current,
+            voltage,
+            max_current,
+        })
+    }
+}
+
+impl ToBytes for UcsiFixedSupplyPdo {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write_bit(self.dual_role_power)?;
+        bit_writer.write_bit(self.usb_suspend_supported)?;
+        bit_writer.write_bit(self.unconstrained_power)?;
+        bit_writer.write_bit(self.usb_comms_capable)?;
+        bit_writer.write_bit(self.dual_role_data)?;
+        bit_writer.write_bit(self.epr_capable)?;
+        bit_writer.write(2, 0u32)?; // Reserved bits
+        bit_writer.write(2, self.peak_current)?;
+        bit_writer.write(10, self.voltage.0 / 50)?;
+        bit_writer.write(10, self.max_current.0 / 10)?;
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf)]
+/// A Battery Supply Power Data Object (`pdo_type` `01`). See USB PD
+/// Specification - Table 6.7 "Power Data Object".
+pub struct UcsiBatterySupplyPdo {
+    /// 50 mV units.
+    pub max_voltage: crate::Millivolt,
+    /// 50 mV units.
+    pub min_voltage: crate::Millivolt,
+    /// 250 mW units.
+    pub max_power: crate::Milliwatt,
+}
+
+impl FromBytes for UcsiBatterySupplyPdo {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let max_voltage = (reader.read::<u32>(10)? * 50).into();
+        let min_voltage = (reader.read::<u32>(10)? * 50).into();
+        let max_power = (reader.read::<u32>(10)? * 250).into();
+
+        Ok(Self {
+            max_voltage,
+            min_voltage,
+            max_power,
+        })
+    }
+}
+
+impl ToBytes for UcsiBatterySupplyPdo {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write(10, self.max_voltage.0 / 50)?;
+        bit_writer.write(10, self.min_voltage.0 / 50)?;
+        bit_writer.write(10, self.max_power.0 / 250)?;
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf)]
+/// A Variable Supply (non-battery) Power Data Object (`pdo_type` `10`). See
+/// USB PD Specification - Table 6.7 "Power Data Object".
+pub struct UcsiVariableSupplyPdo {
+    /// 50 mV units.
+    pub max_voltage: crate::Millivolt,
+    /// 50 mV units.
+    pub min_voltage: crate::Millivolt,
+    /// 10 mA units.
+    pub max_current: crate::Milliamp,
+}
+
+impl FromBytes for UcsiVariableSupplyPdo {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let max_voltage = (reader.read::<u32>(10)? * 50).into();
+        let min_voltage = (reader.read::<u32>(10)? * 50).into();
+        let max_current = (reader.read::<u32>(10)? * 10).into();
+
+        Ok(Self {
+            max_voltage,
+            min_voltage,
+            max_current,
+        })
+    }
+}
+
+impl ToBytes for UcsiVariableSupplyPdo {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write(10, self.max_voltage.0 / 50)?;
+        bit_writer.write(10, self.min_voltage.0 / 50)?;
+        bit_writer.write(10, self.max_current.0 / 10)?;
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf)]
+/// A Programmable Power Supply Augmented PDO (`pdo_type` `11`, APDO
+/// `subtype` `00`). See USB PD Specification - Table 6.9 "SPR Programmable
+/// Power Supply APDO".
+pub struct UcsiPpsPdo {
+    /// 100 mV units.
+    pub max_voltage: crate::Millivolt,
+    /// 100 mV units.
+    pub min_voltage: crate::Millivolt,
+    /// 50 mA units.
+    pub max_current: crate::Milliamp,
+}
+
+impl FromBytes for UcsiPpsPdo {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        reader.skip(3)?; // Reserved bits
+        let max_voltage = (reader.read::<u32>(8)? * 100).into();
+        reader.skip(1)?; // Reserved bit
+        let min_voltage = (reader.read::<u32>(8)? * 100).into();
+        reader.skip(1)?; // Reserved bit
+        let max_current = (reader.read::<u32>(7)? * 50).into();
+
+        Ok(Self {
+            max_voltage,
+            min_voltage,
+            max_current,
+        })
+    }
+}
+
+impl ToBytes for UcsiPpsPdo {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write(3, 0u32)?; // Reserved bits
+        bit_writer.write(8, self.max_voltage.0 / 100)?;
+        bit_writer.write(1, 0u32)?; // Reserved bit
+        bit_writer.write(8, self.min_voltage.0 / 100)?;
+        bit_writer.write(1, 0u32)?; // Reserved bit
+        bit_writer.write(7, self.max_current.0 / 50)?;
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, Printf, Snprintf)]
+/// A Power Data Object as returned by `GET_PDOS`, decoded from its type tag
+/// (bits 31:30) per USB PD Specification - Table 6.7 "Power Data Object".
+pub enum UcsiPdo {
+    Fixed(UcsiFixedSupplyPdo),
+    Battery(UcsiBatterySupplyPdo),
+    Variable(UcsiVariableSupplyPdo),
+    /// APDO `subtype` `00`.
+    AugmentedPps(UcsiPpsPdo),
+    /// An Augmented PDO whose `subtype` (bits 29:28) isn't recognized by
+    /// this version of the library (the PD spec keeps adding SPR/EPR
+    /// subtypes). The remaining 28 bits are preserved as-is instead of
+    /// failing the parse, so callers can still inspect the raw payload.
+    AugmentedUnknown { subtype: u32, raw: u32 },
+}
+
+impl FromBytes for UcsiPdo {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        let pdo_type = reader.read::<u32>(2)?;
+        match pdo_type {
+            0 => Ok(UcsiPdo::Fixed(UcsiFixedSupplyPdo::from_bytes(reader)?)),
+            1 => Ok(UcsiPdo::Battery(UcsiBatterySupplyPdo::from_bytes(reader)?)),
+            2 => Ok(UcsiPdo::Variable(UcsiVariableSupplyPdo::from_bytes(reader)?)),
+            3 => {
+                let subtype = reader.read::<u32>(2)?;
+                match subtype {
+                    0 => Ok(UcsiPdo::AugmentedPps(UcsiPpsPdo::from_bytes(reader)?)),
+                    other => {
+                        let raw = reader.read::<u32>(28)?;
+                        Ok(UcsiPdo::AugmentedUnknown { subtype: other, raw })
+                    }
+                }
+            }
+            other => Err(Error::ParseError {
+                field: "pdo_type (i.e.: bits31..30)".into(),
+                value: other,
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            }),
+        }
+    }
+}
+
+impl ToBytes for UcsiPdo {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        match self {
+            UcsiPdo::Fixed(pdo) => {
+                bit_writer.write(2, 0u32)?;
+                pdo.to_bytes(bit_writer)
+            }
+            UcsiPdo::Battery(pdo) => {
+                bit_writer.write(2, 1u32)?;
+                pdo.to_bytes(bit_writer)
+            }
+            UcsiPdo::Variable(pdo) => {
+                bit_writer.write(2, 2u32)?;
+                pdo.to_bytes(bit_writer)
+            }
+            UcsiPdo::AugmentedPps(pdo) => {
+                bit_writer.write(2, 3u32)?;
+                bit_writer.write(2, 0u32)?;
+                pdo.to_bytes(bit_writer)
+            }
+            UcsiPdo::AugmentedUnknown { subtype, raw } => {
+                bit_writer.write(2, 3u32)?;
+                bit_writer.write(2, *subtype)?;
+                bit_writer.write(28, *raw)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// Encodes `value` then decodes it back, asserting the result matches.
+    /// Several of these structs' bit widths don't sum to a byte boundary, so
+    /// this checks struct-level equality rather than the raw encoded bytes.
+    fn round_trip<T: FromBytes + ToBytes + PartialEq + std::fmt::Debug>(value: T, byte_len: usize) {
+        let mut encoded = vec![0u8; byte_len];
+        let mut writer = BitWriter::new(Cursor::new(&mut encoded[..]));
+        value.to_bytes(&mut writer).unwrap();
+        writer.byte_align().unwrap();
+
+        let mut reader = BitReader::new(Cursor::new(&encoded[..]));
+        let decoded = T::from_bytes(&mut reader).unwrap();
+
+        assert_eq!(value, decoded);
+    }
+
+    #[test]
+    fn round_trip_connector_status_change() {
+        round_trip(
+            ConnectorStatusChange {
+                reserved1: false,
+                external_supply_change: true,
+                power_operation_mode_change: false,
+                attention: true,
+                reserved2: false,
+                supported_provider_capabilities_change: true,
+                negotiated_power_level_change: false,
+                pd_reset_complete: true,
+                supported_cam_change: false,
+                battery_charging_status_change: true,
+                reserved3: false,
+                connector_partner_changed: true,
+            },
+            2,
+        );
+    }
+
+    #[test]
+    fn round_trip_cable_property() {
+        round_trip(
+            UcsiCableProperty {
+                speed_exponent: CablePropertySpeedExponent::Mbps,
+                speed_mantissa: 100,
+                b_current_capability: 3,
+                vbus_in_cable: 1,
+                cable_type: CablePropertyType::n(1).unwrap(),
+                directionality: 1,
+                plug_end_type: CablePropertyPlugEndType::n(1).unwrap(),
+                mode_support: true,
+                cable_pd_revision: 2,
+                latency: 8,
+            },
+            5,
+        );
+    }
+
+    #[test]
+    fn round_trip_alternate_mode() {
+        round_trip(
+            UcsiAlternateMode {
+                svid: [DISPLAYPORT_SVID, THUNDERBOLT_SVID],
+                vdo: [0x1234_5678, 0x0000_0001],
+            },
+            12,
+        );
+    }
+
+    #[test]
+    fn round_trip_cam_supported() {
+        round_trip(UcsiCamSupported { cam_supported: true }, 1);
+    }
+
+    #[test]
+    fn round_trip_current_cam() {
+        let mut current_alternate_mode = [0usize; UCSI_MAX_NUM_ALT_MODE];
+        current_alternate_mode[0] = 3;
+        round_trip(UcsiCurrentCam { current_alternate_mode }, UCSI_MAX_NUM_ALT_MODE);
+    }
+
+    #[test]
+    fn round_trip_connector_status() {
+        round_trip(
+            UcsiConnectorStatus {
+                connector_status_change: ConnectorStatusChange {
+                    connector_partner_changed: true,
+                    ..Default::default()
+                },
+                power_operation_mode: PowerOperationMode::PowerDelivery,
+                connect_status: true,
+                power_direction: PowerDirection::Provider,
+                connector_partner_flags: 0x12,
+                connector_partner_type: ConnectorPartnerType::UfpAttached,
+                negotiated_power_level: 0x1234_5678,
+                battery_charging_capability_status: BatteryChargingCapabilityStatus::NominalChargingRate,
+                provider_capabilities_limited_reason: 0x5,
+                pd_version_operation_mode: 0x0310,
+                orientation: ConnectorOrientation::Reverse,
+                sink_path_status: SinkPathStatus::Ready,
+                reverse_current_protection_status: true,
+                power_reading_ready: true,
+                scale_current: 0x2,
+                peak_current: 0x1234,
+                average_current: 0x5678,
+                scale_voltage: 0x1,
+                voltage_reading: 0x0190,
+            },
+            21,
+        );
+    }
+
+    #[test]
+    fn round_trip_connector_capability_unknown_operation_mode() {
+        // A newer PPM reporting a mode this library doesn't know about yet
+        // should round-trip as `Unknown` instead of failing the parse.
+        round_trip(
+            UcsiConnectorCapability {
+                operation_mode: ConnectorCapabilityOperationMode::Unknown(200),
+                provider: true,
+                consumer: false,
+                swap_to_dfp: false,
+                swap_to_ufp: true,
+                swap_to_src: false,
+                swap_to_snk: true,
+                extended_operation_mode: ConnectorCapabilityExtendedOperationMode::Unknown(100),
+                miscellaneous_capabilities: ConnectorCapabilityMiscellaneousCapabilities::Security,
+                reverse_current_protection_support: true,
+                partner_pd_revision: 3,
+            },
+            4,
+        );
+    }
+
+    #[test]
+    fn bm_optional_features_versioned_decode() {
+        let value = UcsiBmOptionalFeatures {
+            set_retimer_mode_supported: true,
+            chunking_supported: true,
+            ..Default::default()
+        };
+
+        // From UCSI 2.1 on both trailing bits are defined, so they
+        // round-trip like any other field.
+        let mut encoded = vec![0u8; 3];
+        let mut writer = BitWriter::new(Cursor::new(&mut encoded[..]));
+        value.to_bytes_versioned(&mut writer, Some(crate::UcsiVersion(0x0210))).unwrap();
+        writer.byte_align().unwrap();
+        let mut reader = BitReader::new(Cursor::new(&encoded[..]));
+        let decoded = UcsiBmOptionalFeatures::from_bytes_versioned(&mut reader, Some(crate::UcsiVersion(0x0210))).unwrap();
+        assert_eq!(value, decoded);
+
+        // At UCSI 1.2 neither bit is defined yet, so a PPM negotiated at
+        // that revision never sets them, regardless of what the struct says.
+        let mut encoded = vec![0u8; 3];
+        let mut writer = BitWriter::new(Cursor::new(&mut encoded[..]));
+        value.to_bytes_versioned(&mut writer, Some(crate::UcsiVersion(0x0120))).unwrap();
+        writer.byte_align().unwrap();
+        let mut reader = BitReader::new(Cursor::new(&encoded[..]));
+        let decoded = UcsiBmOptionalFeatures::from_bytes_versioned(&mut reader, Some(crate::UcsiVersion(0x0120))).unwrap();
+        assert!(!decoded.set_retimer_mode_supported);
+        assert!(!decoded.chunking_supported);
+    }
+
+    #[test]
+    fn round_trip_fixed_supply_pdo() {
+        round_trip(
+            UcsiPdo::Fixed(UcsiFixedSupplyPdo {
+                dual_role_power: true,
+                usb_suspend_supported: false,
+                unconstrained_power: true,
+                usb_comms_capable: false,
+                dual_role_data: true,
+                epr_capable: false,
+                peak_current: 2,
+                voltage: crate::Millivolt(5000),
+                max_current: crate::Milliamp(3000),
+            }),
+            4,
+        );
+    }
+
+    #[test]
+    fn round_trip_battery_supply_pdo() {
+        round_trip(
+            UcsiPdo::Battery(UcsiBatterySupplyPdo {
+                max_voltage: crate::Millivolt(20000),
+                min_voltage: crate::Millivolt(5000),
+                max_power: crate::Milliwatt(60000),
+            }),
+            4,
+        );
+    }
+
+    #[test]
+    fn round_trip_variable_supply_pdo() {
+        round_trip(
+            UcsiPdo::Variable(UcsiVariableSupplyPdo {
+                max_voltage: crate::Millivolt(20000),
+                min_voltage: crate::Millivolt(5000),
+                max_current: crate::Milliamp(3000),
+            }),
+            4,
+        );
+    }
+
+    #[test]
+    fn round_trip_pps_pdo() {
+        round_trip(
+            UcsiPdo::AugmentedPps(UcsiPpsPdo {
+                max_voltage: crate::Millivolt(11000),
+                min_voltage: crate::Millivolt(3300),
+                max_current: crate::Milliamp(3000),
+            }),
+            4,
+        );
+    }
+
+    #[test]
+    fn round_trip_augmented_unknown_pdo() {
+        // A future SPR/EPR APDO subtype this library doesn't know how to
+        // interpret yet should still round-trip via its raw payload.
+        round_trip(UcsiPdo::AugmentedUnknown { subtype: 2, raw: 0x0abc_def0 }, 4);
+    }
+
+    #[test]
+    fn decode_pdos_reads_a_back_to_back_array_of_pdos() {
+        let fixed = UcsiPdo::Fixed(UcsiFixedSupplyPdo {
+            dual_role_power: true,
+            usb_suspend_supported: false,
+            unconstrained_power: true,
+            usb_comms_capable: false,
+            dual_role_data: true,
+            epr_capable: false,
+            peak_current: 2,
+            voltage: crate::Millivolt(5000),
+            max_current: crate::Milliamp(3000),
+        });
+        let variable = UcsiPdo::Variable(UcsiVariableSupplyPdo {
+            max_voltage: crate::Millivolt(20000),
+            min_voltage: crate::Millivolt(5000),
+            max_current: crate::Milliamp(3000),
+        });
+
+        let mut data = vec![0u8; 8];
+        let mut writer = BitWriter::new(Cursor::new(&mut data[..4]));
+        fixed.to_bytes(&mut writer).unwrap();
+        writer.byte_align().unwrap();
+        let mut writer = BitWriter::new(Cursor::new(&mut data[4..]));
+        variable.to_bytes(&mut writer).unwrap();
+        writer.byte_align().unwrap();
+
+        assert_eq!(decode_pdos(&data).unwrap(), vec![fixed, variable]);
+    }
+}
@@ -4,6 +4,9 @@
 
 //! The VDO data structures
 
+use bitstream_io::BitRead;
+use bitstream_io::BitWrite;
+use enumn::N;
 use proc_macros::CApiWrapper;
 use proc_macros::Printf;
 use proc_macros::Snprintf;
@@ -11,6 +14,13 @@ use proc_macros::Snprintf;
 use crate::pd::pd3p2::vdo::CertStat;
 use crate::pd::pd3p2::vdo::Dfp;
 use crate::pd::pd3p2::vdo::IdHeader;
+use crate::pd::pd3p2::DiscoverIdentityResponse;
+use crate::BitReader;
+use crate::BitWriter;
+use crate::Error;
+use crate::FromBytes;
+use crate::Result;
+use crate::ToBytes;
 
 #[cfg(feature = "c_api")]
 pub(crate) mod c_api {
@@ -29,6 +39,7 @@ use crate::pd::pd3p2::vdo::ProductType;
 use crate::pd::pd3p2::vdo::Ufp;
 use crate::pd::pd3p2::vdo::Vpd;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, CApiWrapper)]
 #[c_api(prefix = "TypeCRs", repr_c = true)]
 /// A type representing the different types of VDO supported by the library.
@@ -46,3 +57,267 @@ pub enum Vdo {
     #[c_api(variant_prefix = "Pd3p2Vdo")]
     Pd3p2Dfp(Dfp),
 }
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Printf, Snprintf, N)]
+/// The cable's highest supported USB signaling speed, from the Cable VDO's
+/// "USB Highest Speed" field.
+pub enum CableSpeed {
+    #[default]
+    Usb2_0 = 0,
+    Usb3_2Gen1 = 1,
+    Usb3_2Gen2OrUsb4Gen2 = 2,
+    Usb4Gen3 = 3,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default, CApiWrapper)]
+#[c_api(prefix = "Pd", repr_c = true)]
+/// The Cable VDO: the Product Type VDO carried by a passive or active
+/// cable's Discover Identity ACK, describing the electrical capabilities of
+/// the cable assembly itself rather than the plug's identity (see
+/// [`Vdo::Pd3p2ProductType`] for the latter).
+pub struct CableVdo {
+    /// Maximum VBUS voltage the cable is rated to carry.
+    pub max_vbus_voltage: crate::Millivolt,
+    /// Maximum current the cable is rated to carry.
+    pub max_current: crate::Milliamp,
+    /// The cable's highest supported USB signaling speed.
+    pub cable_speed: CableSpeed,
+    /// Round-trip cable propagation delay, as the raw 3-bit bucket defined
+    /// by the spec (longer cables report higher values).
+    pub cable_latency: u8,
+    /// Whether the cable has a second, dedicated SOP'' controller (true
+    /// for most active cables, false for passive ones).
+    pub sop_pp_supported: bool,
+}
+
+impl FromBytes for CableVdo {
+    fn from_bytes(reader: &mut BitReader) -> Result<Self> {
+        reader.skip(2)?; // bits31..30: reserved
+        let max_vbus_voltage = reader.read::<u32>(2)?; // bits29..28: max VBUS voltage
+        let max_vbus_voltage = match max_vbus_voltage {
+            0 => 20_000,
+            1 => 30_000,
+            2 => 40_000,
+            _ => 50_000,
+        }
+        .into();
+        reader.skip(3)?; // bits27..25: reserved
+        let sop_pp_supported = reader.read_bit()?; // bit24: SOP'' controller present
+        reader.skip(8)?; // bits23..16: reserved
+        let max_current = (reader.read::<u32>(8)? * 50).into(); // bits15..8: max current, 50mA units
+        let cable_latency = reader.read::<u32>(3)? as u8; // bits7..5: cable latency
+        let cable_speed = reader.read::<u32>(3)?; // bits4..2: USB highest speed
+        let cable_speed = CableSpeed::n(cable_speed).ok_or_else(|| Error::ParseError {
+            field: "cable_speed".into(),
+            value: cable_speed,
+            #[cfg(feature = "backtrace")]
+            backtrace: crate::backtrace::Backtrace::capture(),
+        })?;
+        reader.skip(2)?; // bits1..0: reserved
+
+        Ok(Self {
+            max_vbus_voltage,
+            max_current,
+            cable_speed,
+            cable_latency,
+            sop_pp_supported,
+        })
+    }
+}
+
+impl ToBytes for CableVdo {
+    fn to_bytes(&self, bit_writer: &mut BitWriter) -> Result<()> {
+        bit_writer.write(2, 0u32)?; // bits31..30: reserved
+        let max_vbus_voltage = match self.max_vbus_voltage.0 {
+            0..=20_000 => 0u32,
+            20_001..=30_000 => 1,
+            30_001..=40_000 => 2,
+            _ => 3,
+        };
+        bit_writer.write(2, max_vbus_voltage)?;
+        bit_writer.write(3, 0u32)?; // bits27..25: reserved
+        bit_writer.write_bit(self.sop_pp_supported)?;
+        bit_writer.write(8, 0u32)?; // bits23..16: reserved
+        bit_writer.write(8, self.max_current.0 / 50)?;
+        bit_writer.write(3, self.cable_latency as u32)?;
+        bit_writer.write(3, self.cable_speed as u32)?;
+        bit_writer.write(2, 0u32)?; // bits1..0: reserved
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, CApiWrapper)]
+#[c_api(prefix = "Pd", repr_c = true)]
+/// The cable's Discover Identity ACK (SOP'): the ID Header, Cert Stat and
+/// Product VDOs common to any identity response, plus the [`CableVdo`]
+/// unique to a cable's Product Type VDO slot.
+pub struct CableIdentity {
+    /// The cable's ID Header, Cert Stat and Product VDOs, decoded the same
+    /// way as a partner's Discover Identity ACK.
+    pub identity: DiscoverIdentityResponse,
+    /// The cable assembly's own electrical capabilities (max VBUS voltage,
+    /// max current, speed, latency, SOP'' presence).
+    pub cable_vdo: CableVdo,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, CApiWrapper)]
+#[c_api(prefix = "Pd", repr_c = true)]
+/// A complete Vendor Defined Message as it arrives on the wire: the
+/// [`crate::pd::VdmHeader`] plus its data-object payload. Where the payload
+/// is recognized (currently a Discover Identity ACK), the objects are
+/// decoded into [`Vdo`]; otherwise they are kept as raw 32-bit words.
+pub struct Vdm {
+    /// The VDM header.
+    pub header: crate::pd::VdmHeader,
+    /// The VDM's data objects, decoded where their shape is known.
+    pub objects: Vec<Vdo>,
+    /// Any trailing objects that couldn't be mapped to a [`Vdo`] variant,
+    /// in wire order, following whatever was consumed into `objects`.
+    pub raw_objects: Vec<u32>,
+}
+
+impl Vdm {
+    /// Parses a VDM header followed by `num_objects - 1` 4-byte data
+    /// objects (the header itself is the first data object on the wire).
+    /// Rejects `num_objects` outside `1..=7`, matching the 3-bit Number of
+    /// Data Objects field of the PD message header that supplies it.
+    pub fn from_bytes(reader: &mut BitReader, num_objects: u8) -> Result<Self> {
+        if !(1..=7).contains(&num_objects) {
+            return Err(Error::ParseError {
+                field: "num_objects".into(),
+                value: num_objects as u32,
+                #[cfg(feature = "backtrace")]
+                backtrace: crate::backtrace::Backtrace::capture(),
+            });
+        }
+
+        let header = crate::pd::VdmHeader::from_bytes(reader)?;
+        let remaining = num_objects - 1;
+
+        let mut objects = Vec::new();
+        let mut raw_objects = Vec::new();
+
+        if header.structured
+            && header.command == crate::pd::Command::DiscoverIdentity
+            && header.command_type == crate::pd::CommandType::Ack
+        {
+            // Discover Identity ACK: ID Header, Cert Stat and Product VDOs
+            // always come first, in that order.
+            if remaining >= 1 {
+                objects.push(Vdo::Pd3p2IdHeader(IdHeader::from_bytes(reader)?));
+            }
+            if remaining >= 2 {
+                objects.push(Vdo::Pd3p2CertStat(CertStat::from_bytes(reader)?));
+            }
+            if remaining >= 3 {
+                objects.push(Vdo::Pd3p2ProductType(ProductType::from_bytes(reader)?));
+            }
+            // A 4th object, when present, is a UFP, DFP or VPD Product Type
+            // VDO, selected by the ID Header already parsed into
+            // `objects[0]`: USB PD Specification table 6-29 says a nonzero
+            // Product Type (DFP) means a DFP VDO, and (failing that) a
+            // Product Type (UFP) of 5 (VPD) means a VPD VDO; everything
+            // else is a UFP VDO.
+            // `objects` is empty when the ACK carries only the VDM header
+            // (`num_objects == 1`); the loop below then doesn't run.
+            const VPD_PRODUCT_TYPE_UFP: u8 = 5;
+            let (is_dfp, is_vpd) = match objects.first() {
+                Some(Vdo::Pd3p2IdHeader(id_header)) => (
+                    id_header.product_type_dfp != 0,
+                    id_header.product_type_ufp == VPD_PRODUCT_TYPE_UFP,
+                ),
+                _ => (false, false),
+            };
+            for _ in 4..=remaining {
+                objects.push(if is_dfp {
+                    Vdo::Pd3p2Dfp(Dfp::from_bytes(reader)?)
+                } else if is_vpd {
+                    Vdo::Pd3p2Vpd(Vpd::from_bytes(reader)?)
+                } else {
+                    Vdo::Pd3p2Ufp(Ufp::from_bytes(reader)?)
+                });
+            }
+        } else {
+            for _ in 0..remaining {
+                raw_objects.push(reader.read::<u32>(32)?);
+            }
+        }
+
+        Ok(Self {
+            header,
+            objects,
+            raw_objects,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::pd::Command;
+    use crate::pd::CommandType;
+    use crate::pd::VdmHeader;
+
+    fn discover_identity_ack_header() -> VdmHeader {
+        VdmHeader {
+            vendor_id: 0xff00,
+            structured: true,
+            version: 0,
+            reserved0: (),
+            object_position: 0,
+            command_type: CommandType::Ack,
+            reserved1: (),
+            command: Command::DiscoverIdentity,
+        }
+    }
+
+    /// USB PD Specification table 6-29: bits29..27 are Product Type (UFP),
+    /// bits25..23 are Product Type (DFP). Every other bit is left at zero.
+    fn id_header_word(product_type_ufp: u32, product_type_dfp: u32) -> u32 {
+        (product_type_ufp << 27) | (product_type_dfp << 23)
+    }
+
+    /// Encodes a 5-object Discover Identity ACK (header, ID Header, Cert
+    /// Stat, Product VDO, Product Type VDO), with the Cert Stat and Product
+    /// VDOs left at zero since this test only cares about the Product Type
+    /// VDO dispatch.
+    fn encode_discover_identity_ack(id_header: u32) -> Vec<u8> {
+        let mut bytes = vec![0u8; 20];
+        let mut writer = BitWriter::new(Cursor::new(&mut bytes[..]));
+        discover_identity_ack_header()
+            .to_bytes(&mut writer)
+            .unwrap();
+        writer.write::<u32>(32, id_header).unwrap();
+        writer.write::<u32>(32, 0).unwrap(); // Cert Stat VDO
+        writer.write::<u32>(32, 0).unwrap(); // Product VDO
+        writer.write::<u32>(32, 0).unwrap(); // Product Type VDO
+        bytes
+    }
+
+    #[test]
+    fn discover_identity_ack_decodes_ufp_product_type_vdo() {
+        let bytes = encode_discover_identity_ack(id_header_word(2, 0));
+        let mut reader = BitReader::new(Cursor::new(&bytes[..]));
+        let vdm = Vdm::from_bytes(&mut reader, 5).unwrap();
+
+        assert_eq!(vdm.objects.len(), 4);
+        assert!(matches!(vdm.objects[3], Vdo::Pd3p2Ufp(_)));
+    }
+
+    #[test]
+    fn discover_identity_ack_decodes_dfp_product_type_vdo() {
+        let bytes = encode_discover_identity_ack(id_header_word(2, 2));
+        let mut reader = BitReader::new(Cursor::new(&bytes[..]));
+        let vdm = Vdm::from_bytes(&mut reader, 5).unwrap();
+
+        assert_eq!(vdm.objects.len(), 4);
+        assert!(matches!(vdm.objects[3], Vdo::Pd3p2Dfp(_)));
+    }
+}